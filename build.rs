@@ -1,23 +1,277 @@
+use std::ffi::OsStr;
 use std::path::Path;
 use std::{env, fs};
 
 fn main() {
     let out_dir = env::var_os("OUT_DIR").unwrap();
-    let dest_path = Path::new(&out_dir).join("default_word_list.rs");
+    let out_dir = Path::new(&out_dir);
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let crate_dir = Path::new(&crate_dir);
 
-    let word_list: Vec<_> = include_str!("./dictionary.txt").lines().collect();
+    let answers: Vec<_> = include_str!("./dictionary.txt").lines().collect();
+    let embedded_answers = if cfg!(feature = "embed-answers") {
+        answers.as_slice()
+    } else {
+        &[]
+    };
+    let guesses = if cfg!(feature = "embed-guesses") {
+        println!("cargo:rerun-if-changed=guesses.txt");
+        fs::read_to_string(crate_dir.join("guesses.txt")).unwrap_or_else(|error| {
+            println!(
+                "cargo:warning=embedding no guesses, unable to read guesses.txt: {}",
+                error
+            );
+            String::new()
+        })
+    } else {
+        String::new()
+    };
+    let guesses: Vec<_> = guesses.lines().collect();
 
-    let word_list_len = word_list.len();
-    let word_list_slice = word_list.as_slice();
+    let mut extra_lists = Vec::new();
+    if cfg!(feature = "extra-lists") {
+        println!("cargo:rerun-if-changed=lists");
+        let mut paths: Vec<_> = match fs::read_dir(crate_dir.join("lists")) {
+            Ok(entries) => entries
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| path.extension() == Some(OsStr::new("txt")))
+                .collect(),
+            Err(error) => {
+                println!(
+                    "cargo:warning=embedding no extra lists, unable to read the lists directory: {}",
+                    error
+                );
+                Vec::new()
+            }
+        };
+        paths.sort();
+        for path in paths {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let words = fs::read_to_string(&path).unwrap();
+            let words: Vec<_> = words.lines().collect();
+            extra_lists.push(pack_word_list(
+                out_dir,
+                &name,
+                &format!("extra-{}", name),
+                &words,
+            ));
+        }
+    }
 
+    let extra_lists_len = extra_lists.len();
     fs::write(
-        &dest_path,
+        out_dir.join("embedded_word_lists.rs"),
         format!(
             r"
-pub const DEFAULT_WORD_LIST: [&str; {word_list_len}] = {word_list_slice:?};"
+pub const DEFAULT_WORD_LIST: PackedWordList = {};
+pub const GUESS_WORD_LIST: PackedWordList = {};
+pub const EXTRA_WORD_LISTS: [PackedWordList; {extra_lists_len}] = [{}];",
+            pack_word_list(out_dir, "answers", "answers", embedded_answers),
+            pack_word_list(out_dir, "guesses", "guesses", &guesses),
+            extra_lists.join(", ")
         ),
     )
     .unwrap();
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed=dictionary.txt");
+
+    generate_opening_book(&answers, &out_dir.join("opening_book.rs"));
+
+    #[cfg(feature = "ffi")]
+    generate_c_header();
+}
+
+/// Pack the words back to back into `OUT_DIR/<file_stem>.bin`, returns the expression embedding them
+///
+/// Words that are not [NUM_LETTERS] ascii letters are left out with a warning.
+fn pack_word_list(out_dir: &Path, name: &str, file_stem: &str, words: &[&str]) -> String {
+    let mut bytes = Vec::with_capacity(words.len() * NUM_LETTERS);
+    for word in words.iter().map(|word| word.trim()) {
+        if word.len() == NUM_LETTERS && word.bytes().all(|byte| byte.is_ascii_alphabetic()) {
+            bytes.extend(word.to_ascii_lowercase().bytes());
+        } else if !word.is_empty() {
+            println!(
+                "cargo:warning=leaving {:?} out of the embedded {} list",
+                word, name
+            );
+        }
+    }
+    let file_name = format!("{}.bin", file_stem);
+    fs::write(out_dir.join(&file_name), bytes).unwrap();
+
+    format!(
+        "PackedWordList::new({:?}, include_bytes!(concat!(env!(\"OUT_DIR\"), \"/{}\")))",
+        name, file_name
+    )
+}
+
+/// Write the C header for the `ffi` module to `include/wordle_helper.h`
+#[cfg(feature = "ffi")]
+fn generate_c_header() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let config = cbindgen::Config::from_file(Path::new(&crate_dir).join("cbindgen.toml"))
+        .expect("unable to read cbindgen.toml");
+
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(Path::new(&crate_dir).join("src/ffi.rs"))
+        .generate()
+        .expect("unable to generate c header")
+        .write_to_file(Path::new(&crate_dir).join("include/wordle_helper.h"));
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
+
+/// Letters per word, mirrors `game::NUM_LETTERS`
+const NUM_LETTERS: usize = 5;
+/// Mirrors `game::NUM_RESPONSES`
+const NUM_RESPONSES: usize = 243;
+/// Reply symbols, mirror `game::REPLY_*`
+const REPLY_SUCCESS: u8 = b'+';
+const REPLY_MISS: u8 = b'.';
+const REPLY_PARTIAL: u8 = b'-';
+
+type Response = [u8; NUM_LETTERS];
+
+/// Precompute the entropy strategy's first guess and its second guess for every first reply
+///
+/// Candidates are filtered with the same rules as `HardModeWordPicker::take_turn` and ties go to the alphabetically first word, like `Strategy::Entropy`.
+fn generate_opening_book(word_list: &[&str], dest_path: &Path) {
+    let words: Vec<Vec<u8>> = word_list
+        .iter()
+        .filter(|w| w.len() == NUM_LETTERS && w.chars().all(|c| c.is_ascii_alphabetic()))
+        .map(|w| w.to_ascii_lowercase().into_bytes())
+        .collect();
+    let candidates: Vec<&[u8]> = words.iter().map(Vec::as_slice).collect();
+
+    let first_guess = best_guess(&candidates);
+    let mut replies: Vec<Response> = candidates
+        .iter()
+        .map(|answer| response_for(first_guess, answer))
+        .collect();
+    replies.sort_unstable();
+    replies.dedup();
+
+    let second_guesses: Vec<(String, String)> = replies
+        .iter()
+        .filter_map(|response| {
+            let remaining: Vec<&[u8]> = candidates
+                .iter()
+                .copied()
+                .filter(|word| consistent(word, first_guess, response))
+                .collect();
+            if remaining.is_empty() {
+                return None;
+            }
+
+            Some((
+                String::from_utf8(response.to_vec()).unwrap(),
+                String::from_utf8(best_guess(&remaining).to_vec()).unwrap(),
+            ))
+        })
+        .collect();
+
+    let hash = dictionary_hash(word_list);
+    let first_guess = std::str::from_utf8(first_guess).unwrap();
+    let second_guesses_len = second_guesses.len();
+    fs::write(
+        dest_path,
+        format!(
+            r"
+pub const OPENING_BOOK_DICTIONARY_HASH: u64 = {hash:#x};
+pub const OPENING_BOOK_FIRST_GUESS: &str = {first_guess:?};
+pub const OPENING_BOOK_SECOND_GUESSES: [(&str, &str); {second_guesses_len}] = {second_guesses:?};"
+        ),
+    )
+    .unwrap();
+}
+
+/// FNV-1a over every word followed by a newline, mirrors `opening_book::dictionary_hash`
+fn dictionary_hash(word_list: &[&str]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for word in word_list {
+        for &byte in word.as_bytes().iter().chain(b"\n") {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    hash
+}
+
+/// Mirrors `game::response_for`
+fn response_for(guess: &[u8], solution: &[u8]) -> Response {
+    let mut response = [REPLY_MISS; NUM_LETTERS];
+    let mut unmatched = [0u8; 26];
+    for index in 0..NUM_LETTERS {
+        if guess[index] == solution[index] {
+            response[index] = REPLY_SUCCESS;
+        } else {
+            unmatched[(solution[index] - b'a') as usize] += 1;
+        }
+    }
+    for index in 0..NUM_LETTERS {
+        let letter_index = (guess[index] - b'a') as usize;
+        if response[index] != REPLY_SUCCESS && unmatched[letter_index] > 0 {
+            unmatched[letter_index] -= 1;
+            response[index] = REPLY_PARTIAL;
+        }
+    }
+
+    response
+}
+
+/// If a word survives `HardModeWordPicker::take_turn` for a guess and its response
+fn consistent(word: &[u8], guess: &[u8], response: &Response) -> bool {
+    (0..NUM_LETTERS).all(|index| {
+        let letter = guess[index];
+        match response[index] {
+            REPLY_SUCCESS => word[index] == letter,
+            REPLY_PARTIAL => word.contains(&letter) && word[index] != letter,
+            _ => {
+                let has_matching_repeat = (0..NUM_LETTERS).any(|other| {
+                    other != index && guess[other] == letter && response[other] != REPLY_MISS
+                });
+                if has_matching_repeat {
+                    word[index] != letter
+                } else {
+                    !word.contains(&letter)
+                }
+            }
+        }
+    })
+}
+
+/// The candidate with the most expected information, mirrors `Strategy::Entropy`
+///
+/// Bucket sizes are summed smallest first and ties go to the alphabetically first word, like `GuessEvaluation::new` and `Strategy::Entropy`.
+fn best_guess<'a>(candidates: &[&'a [u8]]) -> &'a [u8] {
+    let total = candidates.len() as f64;
+    let mut best = (candidates[0], f64::MIN);
+    for &guess in candidates {
+        let mut buckets = [0u32; NUM_RESPONSES];
+        for answer in candidates {
+            let response = response_for(guess, answer);
+            let index = response.iter().fold(0, |index, &reply| {
+                index * 3
+                    + match reply {
+                        REPLY_MISS => 0,
+                        REPLY_PARTIAL => 1,
+                        _ => 2,
+                    }
+            });
+            buckets[index] += 1;
+        }
+        let mut sizes: Vec<_> = buckets.into_iter().filter(|&size| size > 0).collect();
+        sizes.sort_unstable();
+        let entropy = sizes.iter().fold(0.0, |entropy, &size| {
+            let probability = size as f64 / total;
+            entropy - probability * probability.log2()
+        });
+        if entropy > best.1 || (entropy == best.1 && guess < best.0) {
+            best = (guess, entropy);
+        }
+    }
+
+    best.0
 }
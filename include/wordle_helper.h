@@ -0,0 +1,167 @@
+#ifndef WORDLE_HELPER_H
+#define WORDLE_HELPER_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Word length for guesses and the dictionary
+ */
+#define WORDLE_NUM_LETTERS 5
+
+/**
+ * Buffer size for a word including its nul terminator
+ */
+#define WORDLE_WORD_SIZE (WORDLE_NUM_LETTERS + 1)
+
+/**
+ * Outcome of a fallible call
+ */
+typedef enum WordleStatus {
+  WORDLE_STATUS_OK = 0,
+  /**
+   * A required pointer argument was null
+   */
+  WORDLE_STATUS_NULL_POINTER,
+  /**
+   * A string argument was not valid UTF-8
+   */
+  WORDLE_STATUS_INVALID_UTF8,
+  /**
+   * Guess is not a word of `WORDLE_NUM_LETTERS` ascii letters
+   */
+  WORDLE_STATUS_INVALID_WORD,
+  /**
+   * Reply is not made of `WORDLE_NUM_LETTERS` of `+` (hit), `-` (partial) and `.` (miss)
+   */
+  WORDLE_STATUS_INVALID_REPLY,
+  /**
+   * Turn leaves no potential solutions, the solver is left unchanged
+   */
+  WORDLE_STATUS_CONTRADICTORY_TURN,
+  /**
+   * There is no turn to undo
+   */
+  WORDLE_STATUS_NOTHING_TO_UNDO,
+} WordleStatus;
+
+/**
+ * Opaque solver handle
+ */
+typedef struct WordleSolver WordleSolver;
+
+/**
+ * A ranked guess, `word` is nul terminated
+ */
+typedef struct WordleSuggestion {
+  char word[WORDLE_WORD_SIZE];
+  /**
+   * Number of distinct letters in the word
+   */
+  size_t score;
+  /**
+   * Chance of the word being the solution, suggestions are ranked by it when the solver has weights
+   */
+  double probability;
+} WordleSuggestion;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Create a solver from `len` nul terminated words, or from the embedded dictionary when `words` is null
+ *
+ * Words that are not valid UTF-8 are skipped. Returns null if any word pointer is null,
+ * or if `words` is null and the library was built without an embedded dictionary (`embed-answers`).
+ * The solver must be released with `wordle_solver_free`.
+ *
+ * # Safety
+ *
+ * `words` must be null or point to `len` pointers to nul terminated strings.
+ */
+struct WordleSolver *wordle_solver_new(const char *const *words,
+                                       size_t len);
+
+/**
+ * Create a solver from `len` nul terminated words where `weights[i]` is the prior probability of `words[i]`
+ *
+ * Words that are not valid UTF-8 are skipped. Returns null if any pointer is null.
+ * The solver must be released with `wordle_solver_free`.
+ *
+ * # Safety
+ *
+ * `words` must point to `len` pointers to nul terminated strings and `weights` to `len` weights.
+ */
+struct WordleSolver *wordle_solver_new_weighted(const char *const *words,
+                                                const double *weights,
+                                                size_t len);
+
+/**
+ * Release a solver, null is ignored
+ *
+ * # Safety
+ *
+ * `solver` must be null or a pointer returned by `wordle_solver_new` that has not been freed.
+ */
+void wordle_solver_free(struct WordleSolver *solver);
+
+/**
+ * Apply a guess (ie. `crane`) and its reply (ie. `.-..+`)
+ *
+ * # Safety
+ *
+ * `solver` must be a live solver, `guess` and `reply` must be nul terminated strings.
+ */
+enum WordleStatus wordle_solver_apply_turn(struct WordleSolver *solver,
+                                           const char *guess,
+                                           const char *reply);
+
+/**
+ * Remove the last turn
+ *
+ * # Safety
+ *
+ * `solver` must be a live solver.
+ */
+enum WordleStatus wordle_solver_undo(struct WordleSolver *solver);
+
+/**
+ * Start over with every word in the dictionary
+ *
+ * # Safety
+ *
+ * `solver` must be a live solver.
+ */
+enum WordleStatus wordle_solver_reset(struct WordleSolver *solver);
+
+/**
+ * How many potential solutions remain, 0 for a null solver
+ *
+ * # Safety
+ *
+ * `solver` must be null or a live solver.
+ */
+size_t wordle_solver_remaining(const struct WordleSolver *solver);
+
+/**
+ * Write up to `capacity` ranked suggestions (best first) into `out`, returning how many were written
+ *
+ * # Safety
+ *
+ * `solver` must be a live solver and `out` must point to at least `capacity` suggestions.
+ */
+size_t wordle_solver_suggestions(const struct WordleSolver *solver,
+                                 struct WordleSuggestion *out,
+                                 size_t capacity);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* WORDLE_HELPER_H */
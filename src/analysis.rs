@@ -0,0 +1,168 @@
+use serde::Serialize;
+
+use crate::evaluation::GuessEvaluation;
+use crate::game::Turn;
+use crate::strategy::Strategy;
+use crate::word_picker::HardModeWordPicker;
+
+/// How a single guess compared to the best guess available at that point
+#[derive(Debug, Clone, Serialize)]
+pub struct TurnAnalysis {
+    pub guess: String,
+    /// The guess with the most expected information among the remaining words (or the guess itself if it was better)
+    pub best_guess: String,
+    /// Expected information of the guess, in bits
+    pub guess_entropy: f64,
+    /// Expected information of the best guess, in bits
+    pub best_entropy: f64,
+    pub remaining_before: usize,
+    pub remaining_after: usize,
+    /// Expected information of the guess as a percentage of the best guess (`0..=100`)
+    pub skill: f64,
+    /// Information actually gained minus the information expected, in bits (positive is lucky)
+    pub luck: f64,
+}
+
+/// Summed prior of the remaining words, their number without priors
+fn remaining_weight(word_picker: &HardModeWordPicker) -> f64 {
+    word_picker
+        .remaining_words()
+        .iter()
+        .map(|word| word_picker.prior(word))
+        .sum()
+}
+
+/// Rate every turn of a finished game against the solver's best guess at that point
+///
+/// `word_picker` is the state the game started from (ie. with past answers removed and priors applied).
+pub fn analyze_game(word_picker: &HardModeWordPicker, turns: &[Turn]) -> Vec<TurnAnalysis> {
+    let mut word_picker = word_picker.clone().without_audit_trail();
+    let mut analysis = Vec::with_capacity(turns.len());
+
+    for turn in turns {
+        let guess: String = turn.iter().map(|cell| cell.letter).collect();
+        let candidates = word_picker.remaining_words();
+        let priors = word_picker.priors();
+        let remaining_before = candidates.len();
+        let weight_before = remaining_weight(&word_picker);
+        let guess_evaluation = GuessEvaluation::weighted(&guess, candidates, priors);
+        let best_evaluation = Strategy::Entropy
+            .pick(&word_picker)
+            .map(|best| GuessEvaluation::weighted(best, candidates, priors))
+            .filter(|best| best.entropy > guess_evaluation.entropy)
+            .unwrap_or_else(|| guess_evaluation.clone());
+
+        word_picker.take_turn(turn.clone());
+        let remaining_after = word_picker.remaining();
+
+        let skill = if best_evaluation.entropy > 0.0 {
+            100.0 * guess_evaluation.entropy / best_evaluation.entropy
+        } else {
+            100.0
+        };
+        let weight_after = remaining_weight(&word_picker);
+        let gained = if weight_after > 0.0 {
+            (weight_before / weight_after).log2()
+        } else {
+            0.0
+        };
+
+        analysis.push(TurnAnalysis {
+            guess,
+            best_guess: best_evaluation.guess,
+            guess_entropy: guess_evaluation.entropy,
+            best_entropy: best_evaluation.entropy,
+            remaining_before,
+            remaining_after,
+            skill,
+            luck: gained - guess_evaluation.entropy,
+        });
+    }
+
+    analysis
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::game::{make_turn, response_for};
+
+    const WORDS: [&str; 4] = ["crane", "crate", "grate", "fuzzy"];
+
+    fn turns(guesses: &[&str], answer: &str) -> Vec<Turn> {
+        guesses
+            .iter()
+            .map(|guess| make_turn(guess, &response_for(guess, answer)))
+            .collect()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    /// Entropy of a split into buckets of these sizes
+    fn entropy(sizes: &[f64]) -> f64 {
+        let total: f64 = sizes.iter().sum();
+        sizes
+            .iter()
+            .map(|size| -(size / total) * (size / total).log2())
+            .sum()
+    }
+
+    #[test]
+    fn guesses_are_rated_against_the_best_guess() {
+        let word_picker = HardModeWordPicker::new(WORDS);
+        let analysis = analyze_game(&word_picker, &turns(&["fuzzy", "crate"], "crate"));
+
+        // fuzzy only splits off itself, crane gets a different reply from every word
+        let first = &analysis[0];
+        assert_eq!(first.best_guess, "crane");
+        assert_eq!((first.remaining_before, first.remaining_after), (4, 3));
+        assert_close(first.guess_entropy, entropy(&[3.0, 1.0]));
+        assert_close(first.best_entropy, 2.0);
+        assert_close(first.skill, 100.0 * entropy(&[3.0, 1.0]) / 2.0);
+        assert_close(first.luck, (4f64 / 3.0).log2() - entropy(&[3.0, 1.0]));
+
+        // Nothing beats crate, which gets the expected information exactly
+        let second = &analysis[1];
+        assert_eq!(second.best_guess, "crate");
+        assert_eq!((second.remaining_before, second.remaining_after), (3, 1));
+        assert_close(second.skill, 100.0);
+        assert_close(second.luck, 0.0);
+    }
+
+    #[test]
+    fn excluded_words_are_not_counted() {
+        let mut word_picker = HardModeWordPicker::new(WORDS);
+        word_picker.remove_words(&HashSet::from([String::from("grate")]));
+        let analysis = analyze_game(&word_picker, &turns(&["fuzzy"], "crate"));
+
+        assert_eq!(
+            (analysis[0].remaining_before, analysis[0].remaining_after),
+            (3, 2)
+        );
+        assert_close(analysis[0].guess_entropy, entropy(&[2.0, 1.0]));
+    }
+
+    #[test]
+    fn priors_weigh_the_information() {
+        let priors = [("crate", 6.0)]
+            .into_iter()
+            .map(|(word, weight)| (word.to_string(), weight))
+            .collect();
+        let word_picker = HardModeWordPicker::new(WORDS).with_priors(Arc::new(priors));
+        let analysis = analyze_game(&word_picker, &turns(&["fuzzy"], "crate"));
+
+        // crane, crate and grate weigh 8 of 9 together
+        assert_close(analysis[0].guess_entropy, entropy(&[8.0, 1.0]));
+        assert_close(analysis[0].luck, (9f64 / 8.0).log2() - entropy(&[8.0, 1.0]));
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::NUM_LETTERS;
+
+/// Everything known about the solution, derived from the replies seen so far
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+pub struct Constraints {
+    /// Letters confirmed in their position (Green)
+    pub placed: [Option<char>; NUM_LETTERS],
+    /// Letters that must appear somewhere in the solution (Yellow or Green)
+    pub required: BTreeSet<char>,
+    /// Positions a letter is known not to occupy
+    pub excluded_positions: BTreeMap<char, BTreeSet<usize>>,
+    /// Letters that do not appear anywhere in the solution (Gray)
+    pub absent: BTreeSet<char>,
+}
+
+impl Constraints {
+    /// Record a letter confirmed to be in a position
+    pub fn place(&mut self, letter: char, position: usize) {
+        self.placed[position] = Some(letter);
+        self.required.insert(letter);
+    }
+
+    /// Record a letter that must appear in the solution but not in this position
+    pub fn misplace(&mut self, letter: char, position: usize) {
+        self.required.insert(letter);
+        self.exclude_position(letter, position);
+    }
+
+    /// Record a position a letter cannot occupy
+    pub fn exclude_position(&mut self, letter: char, position: usize) {
+        self.excluded_positions
+            .entry(letter)
+            .or_default()
+            .insert(position);
+    }
+
+    /// Record a letter that does not appear anywhere in the solution
+    pub fn exclude(&mut self, letter: char) {
+        self.absent.insert(letter);
+    }
+}
+
+/// Why directly entered constraints could not be parsed
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ConstraintsError {
+    /// A clause does not start with green, yellow or gray
+    UnknownColor(String),
+    /// A green or yellow letter has no position
+    MissingPosition(char),
+    /// A position is not a number from 1 to [NUM_LETTERS]
+    IllegalPosition(String),
+    IllegalLetter(String),
+}
+
+impl Display for ConstraintsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConstraintsError::UnknownColor(color) => write!(
+                f,
+                "unknown color {:?}, expected green, yellow or gray",
+                color
+            ),
+            ConstraintsError::MissingPosition(letter) => {
+                write!(f, "missing position for {:?}", letter)
+            }
+            ConstraintsError::IllegalPosition(position) => write!(
+                f,
+                "illegal position {:?}, expected 1 to {}",
+                position, NUM_LETTERS
+            ),
+            ConstraintsError::IllegalLetter(letter) => write!(f, "illegal letter {:?}", letter),
+        }
+    }
+}
+
+impl std::error::Error for ConstraintsError {}
+
+/// Words that only make a green or yellow clause read naturally (`green a in 3`)
+const FILLER_WORDS: [&str; 6] = ["in", "not", "at", "position", "pos", "and"];
+
+/// A one-based position, returned zero-based
+fn parse_position(position: &str) -> Result<usize, ConstraintsError> {
+    position
+        .parse::<usize>()
+        .ok()
+        .filter(|position| (1..=NUM_LETTERS).contains(position))
+        .map(|position| position - 1)
+        .ok_or_else(|| ConstraintsError::IllegalPosition(position.to_string()))
+}
+
+/// Letters with their zero-based position, written as `a3`, `a 3` or `a in 3`
+fn parse_positioned_letters(tokens: &[&str]) -> Result<Vec<(char, usize)>, ConstraintsError> {
+    let mut letters = Vec::new();
+    let mut tokens = tokens.iter().peekable();
+    while let Some(token) = tokens.next() {
+        let mut chars = token.chars();
+        let letter = chars
+            .next()
+            .filter(char::is_ascii_alphabetic)
+            .ok_or_else(|| ConstraintsError::IllegalLetter(token.to_string()))?;
+        let position = match chars.as_str() {
+            "" => tokens
+                .next_if(|token| token.starts_with(|c: char| c.is_ascii_digit()))
+                .ok_or(ConstraintsError::MissingPosition(letter))?,
+            position => position,
+        };
+        letters.push((letter, parse_position(position)?));
+    }
+
+    Ok(letters)
+}
+
+impl FromStr for Constraints {
+    type Err = ConstraintsError;
+
+    /// Parse board knowledge such as `green a in 3, yellow s not in 1, gray r t l`
+    ///
+    /// Clauses are separated by commas or semicolons, positions are one-based.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut constraints = Constraints::default();
+        let input = input.to_ascii_lowercase();
+        for clause in input.split([',', ';']) {
+            let tokens: Vec<_> = clause.split_whitespace().collect();
+            let (color, tokens) = match tokens.split_first() {
+                Some(split) => split,
+                None => continue,
+            };
+            // Gray letters have no position, so a gray `at` or `in` is two letters
+            let positioned: Vec<_> = tokens
+                .iter()
+                .copied()
+                .filter(|token| !FILLER_WORDS.contains(token))
+                .collect();
+
+            match *color {
+                "green" => {
+                    for (letter, position) in parse_positioned_letters(&positioned)? {
+                        constraints.place(letter, position);
+                    }
+                }
+                "yellow" => {
+                    for (letter, position) in parse_positioned_letters(&positioned)? {
+                        constraints.misplace(letter, position);
+                    }
+                }
+                "gray" | "grey" => {
+                    for letter in tokens.iter().flat_map(|token| token.chars()) {
+                        if !letter.is_ascii_alphabetic() {
+                            return Err(ConstraintsError::IllegalLetter(letter.to_string()));
+                        }
+                        constraints.exclude(letter);
+                    }
+                }
+                color => return Err(ConstraintsError::UnknownColor(color.to_string())),
+            }
+        }
+
+        Ok(constraints)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clauses_read_like_a_board() {
+        let constraints: Constraints = "Green a in 3, yellow s not in 1; grey r t l"
+            .parse()
+            .unwrap();
+
+        assert_eq!(constraints.placed, [None, None, Some('a'), None, None]);
+        assert_eq!(constraints.required, BTreeSet::from(['a', 's']));
+        assert_eq!(
+            constraints.excluded_positions,
+            BTreeMap::from([('s', BTreeSet::from([0]))])
+        );
+        assert_eq!(constraints.absent, BTreeSet::from(['l', 'r', 't']));
+    }
+
+    #[test]
+    fn positions_can_be_written_with_the_letter() {
+        let written_apart: Constraints = "yellow e at position 2 and o 4".parse().unwrap();
+        let written_together: Constraints = "yellow e2 o4".parse().unwrap();
+
+        assert_eq!(written_apart, written_together);
+        assert_eq!(written_together.required, BTreeSet::from(['e', 'o']));
+    }
+
+    #[test]
+    fn gray_letters_can_spell_filler_words() {
+        let constraints: Constraints = "gray at, grey in pos".parse().unwrap();
+
+        assert_eq!(
+            constraints.absent,
+            BTreeSet::from(['a', 'i', 'n', 'o', 'p', 's', 't'])
+        );
+    }
+
+    #[test]
+    fn empty_clauses_are_ignored() {
+        assert_eq!(",, ;".parse::<Constraints>(), Ok(Constraints::default()));
+    }
+
+    #[test]
+    fn malformed_clauses_are_rejected() {
+        assert_eq!(
+            "blue a in 1".parse::<Constraints>(),
+            Err(ConstraintsError::UnknownColor("blue".into()))
+        );
+        assert_eq!(
+            "green a".parse::<Constraints>(),
+            Err(ConstraintsError::MissingPosition('a'))
+        );
+        assert_eq!(
+            "green a in 6".parse::<Constraints>(),
+            Err(ConstraintsError::IllegalPosition("6".into()))
+        );
+        assert_eq!(
+            "yellow 3".parse::<Constraints>(),
+            Err(ConstraintsError::IllegalLetter("3".into()))
+        );
+        assert_eq!(
+            "gray r-t".parse::<Constraints>(),
+            Err(ConstraintsError::IllegalLetter("-".into()))
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use std::fmt::{Display, Formatter};
+
+use log::debug;
+
+use crate::game::{GameCell, Reply, Turn};
+use crate::word_picker::HardModeWordPicker;
+
+const REPLIES: [Reply; 3] = [Reply::Miss, Reply::Partial, Reply::Success];
+
+/// A single tile of an entered reply changed to another reply
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Edit {
+    /// Index of the turn in the game
+    pub turn: usize,
+    pub position: usize,
+    /// The reply that was entered
+    pub original: Reply,
+    pub reply: Reply,
+}
+
+/// Replies that differ from the entered ones in a few tiles and leave potential solutions
+#[derive(Debug, Clone)]
+pub struct Correction {
+    pub edits: Vec<Edit>,
+    /// Every turn of the game with the edits made
+    pub turns: Vec<Turn>,
+    /// Potential solutions left after the corrected turns
+    pub remaining: usize,
+}
+
+impl Correction {
+    /// Take the corrected turns on a word picker as it was before the first turn
+    pub fn replay(&self, word_picker: &HardModeWordPicker) -> HardModeWordPicker {
+        let mut word_picker = word_picker.clone();
+        for turn in &self.turns {
+            word_picker.take_turn(turn.clone());
+        }
+
+        word_picker
+    }
+}
+
+fn format_turn(turn: &Turn) -> (String, String) {
+    turn.iter()
+        .map(|cell| (cell.letter, cell.reply.symbol()))
+        .unzip()
+}
+
+impl Display for Correction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut edited_turns: Vec<_> = self.edits.iter().map(|edit| edit.turn).collect();
+        edited_turns.dedup();
+        for (index, &turn) in edited_turns.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            let (guess, reply) = format_turn(&self.turns[turn]);
+            let original: String = reply
+                .chars()
+                .enumerate()
+                .map(|(position, symbol)| {
+                    self.edits
+                        .iter()
+                        .find(|edit| edit.turn == turn && edit.position == position)
+                        .map_or(symbol, |edit| edit.original.symbol())
+                })
+                .collect();
+            write!(f, "turn {} {}: {} -> {}", turn + 1, guess, original, reply)?;
+        }
+
+        write!(f, " ({} remaining)", self.remaining)
+    }
+}
+
+/// Apply edits to the turns, `None` if an edited turn would have won the game
+fn edit_turns(turns: &[Turn], edits: &[Edit]) -> Option<Vec<Turn>> {
+    let mut turns = turns.to_vec();
+    for edit in edits {
+        turns[edit.turn][edit.position].reply = edit.reply;
+    }
+
+    edits
+        .iter()
+        .all(|edit| {
+            turns[edit.turn]
+                .iter()
+                .any(|cell| cell.reply != Reply::Success)
+        })
+        .then_some(turns)
+}
+
+/// Replay the turns with the edits from the first turn they change, `None` if no potential solutions remain
+fn try_edits(
+    prefixes: &[HardModeWordPicker],
+    turns: &[Turn],
+    edits: Vec<Edit>,
+) -> Option<Correction> {
+    let turns = edit_turns(turns, &edits)?;
+    let first = edits.iter().map(|edit| edit.turn).min()?;
+    let mut word_picker = prefixes[first].clone();
+    for turn in &turns[first..] {
+        word_picker.take_turn(turn.clone());
+    }
+
+    (word_picker.remaining() > 0).then(|| Correction {
+        edits,
+        remaining: word_picker.remaining(),
+        turns,
+    })
+}
+
+/// Search the smallest changes to the entered replies that leave potential solutions, fewest changed tiles first
+///
+/// `word_picker` is the state before the first turn, at most `limit` corrections are returned.
+/// The search does not keep an audit trail, [Correction::replay] the chosen correction for one.
+pub fn suggest_corrections(
+    word_picker: &HardModeWordPicker,
+    turns: &[Turn],
+    limit: usize,
+) -> Vec<Correction> {
+    // The word picker before each turn, edits only replay the turns from the first one they change
+    let mut prefixes = vec![word_picker.clone().without_audit_trail()];
+    for turn in turns {
+        let mut word_picker = prefixes[prefixes.len() - 1].clone();
+        word_picker.take_turn(turn.clone());
+        prefixes.push(word_picker);
+    }
+
+    let alternatives: Vec<Edit> = turns
+        .iter()
+        .enumerate()
+        .flat_map(|(turn, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .flat_map(move |(position, &GameCell { reply, .. })| {
+                    REPLIES
+                        .into_iter()
+                        .filter(move |&alternative| alternative != reply)
+                        .map(move |alternative| Edit {
+                            turn,
+                            position,
+                            original: reply,
+                            reply: alternative,
+                        })
+                })
+        })
+        .collect();
+
+    let mut corrections: Vec<_> = alternatives
+        .iter()
+        .filter_map(|&edit| try_edits(&prefixes, turns, vec![edit]))
+        .collect();
+    // Only change two tiles when single changes do not offer enough corrections
+    if corrections.len() < limit {
+        for (index, &first) in alternatives.iter().enumerate() {
+            for &second in &alternatives[index + 1..] {
+                let same_tile = first.turn == second.turn && first.position == second.position;
+                if !same_tile {
+                    corrections.extend(try_edits(&prefixes, turns, vec![first, second]));
+                }
+            }
+        }
+    }
+    debug!("found {} corrections", corrections.len());
+    corrections.truncate(limit);
+
+    corrections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{make_turn, parse_response};
+
+    fn turn(guess: &str, reply: &str) -> Turn {
+        make_turn(guess, &parse_response(reply).unwrap())
+    }
+
+    fn word_picker() -> HardModeWordPicker {
+        HardModeWordPicker::new(["crane", "crate", "slate", "grant"])
+    }
+
+    #[test]
+    fn contradictory_turns_are_corrected() {
+        // crate was entered with its final e as a miss, no word fits
+        let turns = [turn("crane", "+++..")];
+        let mut replayed = word_picker();
+        replayed.take_turn(turns[0].clone());
+        assert_eq!(replayed.remaining(), 0);
+
+        let corrections = suggest_corrections(&word_picker(), &turns, 1);
+        assert_eq!(corrections.len(), 1);
+        let correction = &corrections[0];
+        assert_eq!(
+            correction.edits,
+            [Edit {
+                turn: 0,
+                position: 4,
+                original: Reply::Miss,
+                reply: Reply::Success
+            }]
+        );
+        assert_eq!(correction.turns, [turn("crane", "+++.+")]);
+        assert_eq!(
+            correction.replay(&word_picker()).remaining_words(),
+            ["crate"]
+        );
+        assert_eq!(
+            correction.to_string(),
+            "turn 1 crane: +++.. -> +++.+ (1 remaining)"
+        );
+    }
+
+    #[test]
+    fn single_tile_fixes_come_before_two_tile_fixes() {
+        let corrections = suggest_corrections(&word_picker(), &[turn("crane", "+++..")], 10);
+        let edit_counts: Vec<_> = corrections
+            .iter()
+            .map(|correction| correction.edits.len())
+            .collect();
+
+        assert_eq!(edit_counts, [1, 2]);
+        assert_eq!(corrections[1].turns, [turn("crane", ".+++.")]);
+    }
+
+    #[test]
+    fn corrections_never_win_an_earlier_turn() {
+        let turns = [turn("crane", "++++.")];
+        let edit = Edit {
+            turn: 0,
+            position: 4,
+            original: Reply::Miss,
+            reply: Reply::Success,
+        };
+
+        assert!(edit_turns(&turns, &[edit]).is_none());
+        assert!(edit_turns(
+            &turns,
+            &[Edit {
+                reply: Reply::Partial,
+                ..edit
+            }]
+        )
+        .is_some());
+    }
+
+    #[test]
+    fn replaying_a_correction_keeps_the_audit_trail() {
+        let audited = word_picker().with_audit_trail();
+        let corrections = suggest_corrections(&audited, &[turn("crane", "+++..")], 1);
+        let word_picker = corrections[0].replay(&audited);
+
+        assert!(word_picker.elimination("slate").is_some());
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, Write};
+
+use log::debug;
+use serde::Serialize;
+
+use crate::game::{format_response, make_turn, response_for, Reply, Response, Turn};
+use crate::opening_book;
+use crate::strategy::Strategy;
+use crate::word_picker::HardModeWordPicker;
+
+/// A guess and, for every reply it can get, the guess played next
+#[derive(Debug, Clone, Serialize)]
+pub struct DecisionNode {
+    pub guess: String,
+    /// Potential solutions before the guess is played
+    pub remaining: usize,
+    /// If the guess is one of the potential solutions (so it can be answered with all successes)
+    pub solved: bool,
+    /// The next guess after each reply, keyed by reply symbols (the all-success reply ends the game and is left out)
+    pub children: BTreeMap<String, DecisionNode>,
+}
+
+impl DecisionNode {
+    /// Number of guesses the tree needs for each answer, by answer
+    pub fn guess_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        self.collect_guess_counts(1, &mut counts);
+
+        counts
+    }
+
+    fn collect_guess_counts(&self, depth: usize, counts: &mut BTreeMap<String, usize>) {
+        if self.solved {
+            counts.insert(self.guess.clone(), depth);
+        }
+        for child in self.children.values() {
+            child.collect_guess_counts(depth + 1, counts);
+        }
+    }
+
+    /// Write the tree as a Graphviz digraph, one node per guess and one edge per reply
+    ///
+    /// Guesses that can be the answer are drawn with a double border.
+    pub fn write_dot<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "digraph decision_tree {{")?;
+        writeln!(writer, "    node [shape=box, fontname=monospace];")?;
+        writeln!(writer, "    edge [fontname=monospace];")?;
+        self.write_dot_node(&mut writer, &mut 0)?;
+        writeln!(writer, "}}")
+    }
+
+    /// Write this node and its subtree, returns the node's id
+    fn write_dot_node<W: Write>(&self, writer: &mut W, next_id: &mut usize) -> io::Result<usize> {
+        let id = *next_id;
+        *next_id += 1;
+        writeln!(
+            writer,
+            "    n{} [label=\"{}\\n{}\"{}];",
+            id,
+            self.guess,
+            self.remaining,
+            if self.solved { ", peripheries=2" } else { "" }
+        )?;
+        for (reply, child) in &self.children {
+            let child_id = child.write_dot_node(writer, next_id)?;
+            writeln!(
+                writer,
+                "    n{} -> n{} [label=\"{}\"];",
+                id, child_id, reply
+            )?;
+        }
+
+        Ok(id)
+    }
+}
+
+/// Play the strategy against every word of the dictionary at once, starting with `opener` if given
+///
+/// Returns `None` for an empty dictionary.
+pub fn build_tree(
+    word_list: &[String],
+    strategy: Strategy,
+    opener: Option<&str>,
+) -> Option<DecisionNode> {
+    debug!(
+        "building decision tree over {} words with {}",
+        word_list.len(),
+        strategy
+    );
+    let word_picker = HardModeWordPicker::new(word_list);
+    let in_book = opening_book::applies_to(word_list);
+    let opener = opener
+        .map(String::from)
+        .or_else(|| opening_book::next_guess(strategy, in_book, &word_picker, &[]))?;
+
+    Some(build_node(&word_picker, strategy, in_book, &[], opener))
+}
+
+fn build_node(
+    word_picker: &HardModeWordPicker,
+    strategy: Strategy,
+    in_book: bool,
+    turns: &[Turn],
+    guess: String,
+) -> DecisionNode {
+    let candidates = word_picker.remaining_words();
+    // Bucket by the exact reply, take_turn alone does not cap repeated letters and would let answers into several subtrees
+    let mut buckets: BTreeMap<String, (Response, HashSet<String>)> = BTreeMap::new();
+    for answer in candidates {
+        let response = response_for(&guess, answer);
+        if response.iter().any(|&reply| reply != Reply::Success) {
+            buckets
+                .entry(format_response(&response))
+                .or_insert_with(|| (response, HashSet::new()))
+                .1
+                .insert(answer.clone());
+        }
+    }
+
+    let children = buckets
+        .into_iter()
+        .filter_map(|(symbols, (response, bucket))| {
+            let turn = make_turn(&guess, &response);
+            let mut word_picker = word_picker.clone();
+            word_picker.take_turn(turn.clone());
+            let outside: HashSet<_> = word_picker
+                .remaining_words()
+                .iter()
+                .filter(|word| !bucket.contains(*word))
+                .cloned()
+                .collect();
+            word_picker.remove_words(&outside);
+            let mut turns = turns.to_vec();
+            turns.push(turn);
+            let next_guess = opening_book::next_guess(strategy, in_book, &word_picker, &turns)?;
+
+            Some((
+                symbols,
+                build_node(&word_picker, strategy, in_book, &turns, next_guess),
+            ))
+        })
+        .collect();
+
+    DecisionNode {
+        solved: candidates.contains(&guess),
+        remaining: candidates.len(),
+        guess,
+        children,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn every_answer_is_solved_exactly_once() {
+        // take_turn alone keeps "agora" after "aback" gets `+....` (from "adept"), though it replies `+.-..`
+        let word_list = words(&["aback", "adept", "agora", "ahead", "algae"]);
+        for strategy in Strategy::ALL {
+            let tree = build_tree(&word_list, strategy, Some("aback")).unwrap();
+            let counts = tree.guess_counts();
+
+            let mut solved: Vec<_> = counts.keys().cloned().collect();
+            solved.sort_unstable();
+            let mut expected = word_list.clone();
+            expected.sort_unstable();
+            assert_eq!(solved, expected, "{}", strategy);
+            assert_eq!(count_solved(&tree), word_list.len(), "{}", strategy);
+        }
+    }
+
+    fn count_solved(node: &DecisionNode) -> usize {
+        node.solved as usize + node.children.values().map(count_solved).sum::<usize>()
+    }
+
+    #[test]
+    fn children_hold_only_answers_with_their_reply() {
+        let word_list = words(&["aback", "adept", "agora", "ahead", "algae"]);
+        let tree = build_tree(&word_list, Strategy::Picker, Some("aback")).unwrap();
+
+        let remaining: usize = tree.children.values().map(|child| child.remaining).sum();
+        assert_eq!(remaining + tree.solved as usize, tree.remaining);
+    }
+
+    #[test]
+    fn dot_output_has_a_node_per_guess() {
+        let word_list = words(&["crane", "crate", "slate"]);
+        let tree = build_tree(&word_list, Strategy::Entropy, Some("crane")).unwrap();
+        let mut dot = Vec::new();
+        tree.write_dot(&mut dot).unwrap();
+        let dot = String::from_utf8(dot).unwrap();
+
+        assert!(dot.starts_with("digraph decision_tree {"));
+        // A label for every node and for every edge between them
+        assert_eq!(dot.matches("[label=\"").count(), 2 * count_nodes(&tree) - 1);
+    }
+
+    fn count_nodes(node: &DecisionNode) -> usize {
+        1 + node.children.values().map(count_nodes).sum::<usize>()
+    }
+}
@@ -1,3 +0,0 @@
-
-include!(concat!(env!("OUT_DIR"), "/default_word_list.rs"));
-
@@ -0,0 +1,78 @@
+use std::cmp::Reverse;
+use std::io;
+use std::path::Path;
+
+use wordle_helper::lint::check_dictionary;
+use wordle_helper::stats::DictionaryStats;
+use wordle_helper::word_picker::{ALPHABET, ALPHA_LEN};
+
+/// Print every problem in a dictionary file, returns if it was clean
+pub fn run_check(dictionary_path: &Path) -> io::Result<bool> {
+    let check = check_dictionary(dictionary_path)?;
+    for issue in &check.issues {
+        println!("{}: {}", dictionary_path.display(), issue);
+    }
+
+    let entries = &check.dictionary.entries;
+    let answers = entries.iter().filter(|entry| entry.answer).count();
+    println!(
+        "{} words ({} answers, {} guess-only), {} lines dropped, {} problems",
+        entries.len(),
+        answers,
+        entries.len() - answers,
+        check.dictionary.skipped.len(),
+        check.issues.len()
+    );
+
+    Ok(check.is_clean())
+}
+
+/// Print letter frequencies overall and per position, repeated letters, the most common bigrams and the rarest words
+pub fn run_stats(word_list: &[String], top_len: usize) {
+    let stats = DictionaryStats::new(word_list);
+    let total = stats.words.len().max(1) as f64;
+    println!("{} words", stats.words.len());
+
+    println!("\nLetter  words      1      2      3      4      5  repeated");
+    let mut letters: Vec<_> = (0..ALPHA_LEN).collect();
+    letters.sort_by_key(|&letter| Reverse(stats.letter_frequencies[letter]));
+    for letter in letters {
+        let positions: Vec<_> = stats
+            .positional_frequencies
+            .iter()
+            .map(|frequencies| format!("{:>6}", frequencies[letter]))
+            .collect();
+        println!(
+            "{:>6}  {:>4.1}%  {}  {:>8}",
+            ALPHABET[letter].to_ascii_uppercase(),
+            100.0 * stats.letter_frequencies[letter] as f64 / total,
+            positions.join(" "),
+            stats.repeated_letters[letter]
+        );
+    }
+
+    println!("\nDistinct letters per word");
+    for (distinct, &count) in stats.distinct_letters.iter().enumerate().skip(1) {
+        println!(
+            "{:>6}  {:>5} ({:.1}%)",
+            distinct,
+            count,
+            100.0 * count as f64 / total
+        );
+    }
+
+    let bigrams: Vec<_> = stats
+        .bigrams
+        .iter()
+        .take(top_len)
+        .map(|(bigram, count)| format!("{} {}", bigram, count))
+        .collect();
+    println!("\nCommon bigrams: {}", bigrams.join(", "));
+
+    let rarest: Vec<_> = stats
+        .rarest_words(top_len)
+        .into_iter()
+        .map(|(word, _)| word)
+        .collect();
+    println!("Rarest letters: {}", rarest.join(", "));
+}
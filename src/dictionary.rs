@@ -0,0 +1,657 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use flate2::bufread::GzDecoder;
+use log::{debug, info, warn};
+
+use crate::embedded::{PackedWordList, GUESS_WORD_LIST};
+use crate::{DEFAULT_WORD_LIST, NUM_LETTERS};
+
+/// The dictionary embedded at build time
+pub fn default_word_list() -> Vec<String> {
+    DEFAULT_WORD_LIST.iter().map(|s| s.into()).collect()
+}
+
+/// Prior weight of each (lowercase) word, relative to the other words (ie. a frequency or a probability)
+pub type Priors = HashMap<String, f64>;
+
+/// Weight of words listed without one in a weighted dictionary
+pub const DEFAULT_WEIGHT: f64 = 1.0;
+
+/// First bytes of every gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Load the potential solutions from a dictionary file, see [Dictionary::load]
+pub fn load_word_list<P: AsRef<Path>>(dictionary_path: P) -> io::Result<Vec<String>> {
+    Dictionary::load(dictionary_path).map(|dictionary| dictionary.answers())
+}
+
+/// Open a dictionary file for reading lines, decompressing it if it is gzip compressed
+pub fn open_dictionary<P: AsRef<Path>>(dictionary_path: P) -> io::Result<Box<dyn BufRead>> {
+    let mut reader = BufReader::new(File::open(dictionary_path)?);
+    if reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        debug!("decompressing gzip dictionary");
+        Ok(Box::new(BufReader::new(GzDecoder::new(reader))))
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
+/// Layout of a dictionary file, `#` starts a comment in all of them
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DictionaryFormat {
+    /// One word per line, optionally followed by whitespace and a weight (`raise 0.0013`)
+    Plain,
+    /// Comma separated `word,weight,tags,kind` columns, in any order given a header row naming them
+    Csv,
+    /// Tab separated columns, like [DictionaryFormat::Csv]
+    Tsv,
+}
+
+impl DictionaryFormat {
+    /// Guess the format from a file extension (`.csv`, `.tsv`, anything else is plain), ignoring a trailing `.gz`
+    pub fn from_path(path: &Path) -> Self {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let name = name.strip_suffix(".gz").unwrap_or(&name);
+
+        if name.ends_with(".csv") {
+            DictionaryFormat::Csv
+        } else if name.ends_with(".tsv") {
+            DictionaryFormat::Tsv
+        } else {
+            DictionaryFormat::Plain
+        }
+    }
+}
+
+/// A word and everything its dictionary line says about it
+#[derive(Debug, Clone, PartialEq)]
+pub struct DictionaryEntry {
+    /// Lowercase word
+    pub word: String,
+    pub weight: Option<f64>,
+    pub tags: Vec<String>,
+    /// If the word can be the solution, `false` for words that are only allowed as guesses
+    pub answer: bool,
+    /// One-based line number in the file
+    pub line: usize,
+    /// If the file spelled the word with uppercase letters
+    pub mixed_case: bool,
+}
+
+/// Why a dictionary line was not loaded
+#[derive(Debug, Clone, PartialEq)]
+pub enum SkipReason {
+    /// The word does not have [NUM_LETTERS] letters
+    WrongLength(usize),
+    /// The word contains something other than ascii letters
+    IllegalCharacter(char),
+    /// The weight is not a non-negative number
+    IllegalWeight(String),
+    /// The kind is neither `answer` nor `guess`
+    IllegalKind(String),
+    /// More fields than the format has columns
+    UnexpectedField(String),
+    /// The word was already listed on this earlier line
+    Duplicate(usize),
+    /// The line is not valid UTF-8
+    InvalidUtf8,
+}
+
+impl Display for SkipReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::WrongLength(length) => {
+                write!(f, "word has {} letters, expected {}", length, NUM_LETTERS)
+            }
+            SkipReason::IllegalCharacter(c) => write!(f, "illegal character {:?} in word", c),
+            SkipReason::IllegalWeight(weight) => {
+                write!(
+                    f,
+                    "illegal weight {:?}, expected a non-negative number",
+                    weight
+                )
+            }
+            SkipReason::IllegalKind(kind) => {
+                write!(f, "illegal kind {:?}, expected answer or guess", kind)
+            }
+            SkipReason::UnexpectedField(field) => write!(f, "unexpected field {:?}", field),
+            SkipReason::Duplicate(line) => write!(f, "duplicate of line {}", line),
+            SkipReason::InvalidUtf8 => f.write_str("line is not valid UTF-8"),
+        }
+    }
+}
+
+/// A dictionary line that was not loaded
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedLine {
+    /// One-based line number in the file
+    pub line: usize,
+    pub content: String,
+    pub reason: SkipReason,
+}
+
+impl Display for SkippedLine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {} ({:?}): {}",
+            self.line, self.content, self.reason
+        )
+    }
+}
+
+/// How a dictionary file is combined with the others
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SourceKind {
+    /// Merged in, replacing the embedded dictionary
+    Dictionary,
+    /// Merged in, on top of the dictionaries
+    Include,
+    /// Removed from the combined dictionary
+    Exclude,
+}
+
+/// What a dictionary source did to the combined word list
+#[derive(Debug, Clone)]
+pub struct SourceSummary {
+    pub name: String,
+    pub kind: SourceKind,
+    /// Words loaded from the source
+    pub words: usize,
+    /// Words the source added (or removed, for [SourceKind::Exclude]) that no earlier source did
+    pub changed: usize,
+}
+
+impl Display for SourceSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let verb = match self.kind {
+            SourceKind::Dictionary | SourceKind::Include => "added",
+            SourceKind::Exclude => "removed",
+        };
+        write!(
+            f,
+            "{}: {} of {} words {}",
+            self.name, self.changed, self.words, verb
+        )
+    }
+}
+
+/// Which column holds each field of a csv/tsv dictionary
+#[derive(Debug, Copy, Clone)]
+struct Columns {
+    word: usize,
+    weight: Option<usize>,
+    tags: Option<usize>,
+    kind: Option<usize>,
+    count: usize,
+}
+
+impl Columns {
+    /// Columns without a header row
+    const POSITIONAL: Columns = Columns {
+        word: 0,
+        weight: Some(1),
+        tags: Some(2),
+        kind: Some(3),
+        count: 4,
+    };
+
+    /// Read a header row, `None` if the row has no `word` column (so it is data)
+    fn from_header(fields: &[&str]) -> Option<Self> {
+        let position = |names: &[&str]| {
+            fields
+                .iter()
+                .position(|field| names.contains(&field.to_ascii_lowercase().as_str()))
+        };
+
+        Some(Self {
+            word: position(&["word"])?,
+            weight: position(&["weight", "frequency", "probability"]),
+            tags: position(&["tags"]),
+            kind: position(&["kind", "type"]),
+            count: fields.len(),
+        })
+    }
+}
+
+/// Every word loaded from a dictionary file, and every line that was not
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    pub entries: Vec<DictionaryEntry>,
+    /// Lines that were not loaded, blank and comment lines are not listed
+    pub skipped: Vec<SkippedLine>,
+}
+
+impl Dictionary {
+    /// Load a dictionary file, gzip compressed or not, in the format its extension suggests
+    pub fn load<P: AsRef<Path>>(dictionary_path: P) -> io::Result<Self> {
+        let dictionary_path = dictionary_path.as_ref();
+        debug!("loading dictionary {}", dictionary_path.display());
+        let format = DictionaryFormat::from_path(dictionary_path);
+        let dictionary = Self::parse(open_dictionary(dictionary_path)?, format)?;
+        info!(
+            "processed {:?} dictionary containing {} words",
+            format,
+            dictionary.entries.len()
+        );
+        if !dictionary.skipped.is_empty() {
+            warn!(
+                "skipped {} lines of {}",
+                dictionary.skipped.len(),
+                dictionary_path.display()
+            );
+        }
+
+        Ok(dictionary)
+    }
+
+    /// Parse a dictionary, lines that are not valid entries are skipped and listed
+    pub fn parse<R: BufRead>(reader: R, format: DictionaryFormat) -> io::Result<Self> {
+        let mut dictionary = Self::default();
+        let mut first_lines: HashMap<String, usize> = HashMap::new();
+        let mut columns: Option<Columns> = None;
+
+        // Split bytes rather than strings so a line that is not UTF-8 is skipped instead of failing the whole load
+        for (index, content) in reader.split(b'\n').enumerate() {
+            let mut content = content?;
+            let line = index + 1;
+            if content.last() == Some(&b'\r') {
+                content.pop();
+            }
+            let content = match String::from_utf8(content) {
+                Ok(content) => content,
+                Err(error) => {
+                    dictionary.skipped.push(SkippedLine {
+                        line,
+                        content: String::from_utf8_lossy(error.as_bytes()).into_owned(),
+                        reason: SkipReason::InvalidUtf8,
+                    });
+                    continue;
+                }
+            };
+            let data = content.split('#').next().unwrap_or_default().trim();
+            if data.is_empty() {
+                continue;
+            }
+
+            let entry = match format {
+                DictionaryFormat::Plain => parse_plain(data, line),
+                DictionaryFormat::Csv | DictionaryFormat::Tsv => {
+                    let separator = if format == DictionaryFormat::Csv {
+                        ','
+                    } else {
+                        '\t'
+                    };
+                    let fields: Vec<_> = data
+                        .split(separator)
+                        .map(|field| field.trim().trim_matches('"'))
+                        .collect();
+                    // Only the first data line can be a header
+                    if columns.is_none() {
+                        let header = Columns::from_header(&fields);
+                        columns = Some(header.unwrap_or(Columns::POSITIONAL));
+                        if header.is_some() {
+                            continue;
+                        }
+                    }
+                    parse_columns(&fields, columns.unwrap_or(Columns::POSITIONAL), line)
+                }
+            };
+            let entry = entry.and_then(|entry| match first_lines.get(&entry.word) {
+                Some(&first_line) => Err(SkipReason::Duplicate(first_line)),
+                None => Ok(entry),
+            });
+
+            match entry {
+                Ok(entry) => {
+                    first_lines.insert(entry.word.clone(), line);
+                    dictionary.entries.push(entry);
+                }
+                Err(reason) => dictionary.skipped.push(SkippedLine {
+                    line,
+                    content,
+                    reason,
+                }),
+            }
+        }
+
+        Ok(dictionary)
+    }
+
+    /// The dictionaries embedded at build time, the answers and then any guess-only words
+    pub fn embedded() -> Self {
+        let mut dictionary = Self::from_packed(DEFAULT_WORD_LIST, true);
+        dictionary.merge(Self::from_packed(GUESS_WORD_LIST, false));
+
+        dictionary
+    }
+
+    /// An embedded list, its words are answers unless `answer` is `false`
+    pub fn from_packed(words: PackedWordList, answer: bool) -> Self {
+        let entries = words
+            .iter()
+            .enumerate()
+            .map(|(index, word)| DictionaryEntry {
+                word: word.to_string(),
+                weight: None,
+                tags: Vec::new(),
+                answer,
+                line: index + 1,
+                mixed_case: false,
+            })
+            .collect();
+
+        Self {
+            entries,
+            skipped: Vec::new(),
+        }
+    }
+
+    /// Append the words of another dictionary that are not already listed, returns how many were added
+    ///
+    /// A word already listed keeps its first entry, but becomes an answer if the other dictionary marks it as one.
+    pub fn merge(&mut self, other: Dictionary) -> usize {
+        let mut indices: HashMap<String, usize> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (entry.word.clone(), index))
+            .collect();
+        let before = self.entries.len();
+        for entry in other.entries {
+            match indices.get(&entry.word) {
+                Some(&index) => self.entries[index].answer |= entry.answer,
+                None => {
+                    indices.insert(entry.word.clone(), self.entries.len());
+                    self.entries.push(entry);
+                }
+            }
+        }
+
+        self.entries.len() - before
+    }
+
+    /// Remove every word listed in another dictionary, returns how many were removed
+    pub fn remove(&mut self, other: &Dictionary) -> usize {
+        let words: HashSet<&str> = other
+            .entries
+            .iter()
+            .map(|entry| entry.word.as_str())
+            .collect();
+        let before = self.entries.len();
+        self.entries
+            .retain(|entry| !words.contains(entry.word.as_str()));
+
+        before - self.entries.len()
+    }
+
+    /// Words that can be the solution
+    pub fn answers(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.answer)
+            .map(|entry| entry.word.clone())
+            .collect()
+    }
+
+    /// Every word allowed as a guess, answers included
+    pub fn guesses(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .map(|entry| entry.word.clone())
+            .collect()
+    }
+
+    /// Prior weight of every word, `None` if no entry has a weight (words without one get [DEFAULT_WEIGHT])
+    pub fn priors(&self) -> Option<Priors> {
+        if self.entries.iter().all(|entry| entry.weight.is_none()) {
+            return None;
+        }
+
+        Some(
+            self.entries
+                .iter()
+                .map(|entry| (entry.word.clone(), entry.weight.unwrap_or(DEFAULT_WEIGHT)))
+                .collect(),
+        )
+    }
+}
+
+/// Parse `word [weight]`
+fn parse_plain(data: &str, line: usize) -> Result<DictionaryEntry, SkipReason> {
+    let mut fields = data.split_whitespace();
+    let raw_word = fields.next().unwrap_or_default();
+    let word = parse_word(raw_word)?;
+    let weight = fields.next().map(parse_weight).transpose()?;
+    if let Some(field) = fields.next() {
+        return Err(SkipReason::UnexpectedField(field.to_string()));
+    }
+
+    Ok(DictionaryEntry {
+        weight,
+        tags: Vec::new(),
+        answer: true,
+        line,
+        mixed_case: word != raw_word,
+        word,
+    })
+}
+
+/// Parse the fields of a csv/tsv row, empty fields are missing
+fn parse_columns(
+    fields: &[&str],
+    columns: Columns,
+    line: usize,
+) -> Result<DictionaryEntry, SkipReason> {
+    if let Some(field) = fields
+        .iter()
+        .skip(columns.count)
+        .find(|field| !field.is_empty())
+    {
+        return Err(SkipReason::UnexpectedField(field.to_string()));
+    }
+    let field = |column: Option<usize>| {
+        column
+            .and_then(|column| fields.get(column).copied())
+            .filter(|field| !field.is_empty())
+    };
+
+    let raw_word = field(Some(columns.word)).unwrap_or_default();
+    let word = parse_word(raw_word)?;
+    let weight = field(columns.weight).map(parse_weight).transpose()?;
+    let tags = field(columns.tags)
+        .map(|tags| {
+            tags.split(|c: char| c == ';' || c == '|' || c.is_whitespace())
+                .filter(|tag| !tag.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+    let answer = match field(columns.kind).map(str::to_ascii_lowercase).as_deref() {
+        None | Some("answer") => true,
+        Some("guess") | Some("guess-only") => false,
+        Some(kind) => return Err(SkipReason::IllegalKind(kind.to_string())),
+    };
+
+    Ok(DictionaryEntry {
+        weight,
+        tags,
+        answer,
+        line,
+        mixed_case: word != raw_word,
+        word,
+    })
+}
+
+/// A word of [NUM_LETTERS] ascii letters, lowercased
+fn parse_word(word: &str) -> Result<String, SkipReason> {
+    if let Some(c) = word.chars().find(|c| !c.is_ascii_alphabetic()) {
+        return Err(SkipReason::IllegalCharacter(c));
+    }
+    if word.len() != NUM_LETTERS {
+        return Err(SkipReason::WrongLength(word.len()));
+    }
+
+    Ok(word.to_ascii_lowercase())
+}
+
+/// A finite, non-negative weight
+fn parse_weight(weight: &str) -> Result<f64, SkipReason> {
+    weight
+        .parse::<f64>()
+        .ok()
+        .filter(|weight| weight.is_finite() && *weight >= 0.0)
+        .ok_or_else(|| SkipReason::IllegalWeight(weight.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(data: &str, format: DictionaryFormat) -> Dictionary {
+        Dictionary::parse(data.as_bytes(), format).unwrap()
+    }
+
+    #[test]
+    fn plain_lines_have_a_word_and_an_optional_weight() {
+        let dictionary = parse(
+            "# comment\nCrane 2.5\n\nslate # trailing comment\n",
+            DictionaryFormat::Plain,
+        );
+
+        assert!(dictionary.skipped.is_empty());
+        assert_eq!(dictionary.answers(), ["crane", "slate"]);
+        assert_eq!(dictionary.entries[0].weight, Some(2.5));
+        assert_eq!(dictionary.entries[0].line, 2);
+        assert_eq!(dictionary.entries[1].weight, None);
+        assert_eq!(dictionary.entries[1].line, 4);
+    }
+
+    #[test]
+    fn bad_lines_are_skipped_with_a_reason() {
+        let dictionary = parse(
+            "crane\ncranes\ncr4ne\nslate -1\nslate 1 2\ncrane\n",
+            DictionaryFormat::Plain,
+        );
+        let reasons: Vec<_> = dictionary
+            .skipped
+            .iter()
+            .map(|skipped| (skipped.line, skipped.reason.clone()))
+            .collect();
+
+        assert_eq!(dictionary.answers(), ["crane"]);
+        assert_eq!(
+            reasons,
+            [
+                (2, SkipReason::WrongLength(6)),
+                (3, SkipReason::IllegalCharacter('4')),
+                (4, SkipReason::IllegalWeight("-1".into())),
+                (5, SkipReason::UnexpectedField("2".into())),
+                (6, SkipReason::Duplicate(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn headers_name_the_columns() {
+        let dictionary = parse(
+            "kind,Word,frequency,tags\nguess,aahed,,\nanswer,crane,3,common;bird\n",
+            DictionaryFormat::Csv,
+        );
+
+        assert!(dictionary.skipped.is_empty());
+        assert_eq!(dictionary.answers(), ["crane"]);
+        assert_eq!(dictionary.guesses(), ["aahed", "crane"]);
+        assert_eq!(dictionary.entries[1].tags, ["common", "bird"]);
+        let priors = dictionary.priors().unwrap();
+        assert_eq!(priors["crane"], 3.0);
+        assert_eq!(priors["aahed"], DEFAULT_WEIGHT);
+    }
+
+    #[test]
+    fn columns_are_positional_without_a_header() {
+        let dictionary = parse(
+            "crane\t2\tbird\tanswer\naahed\t\t\tguess\nslate\t1\t\tmaybe\n",
+            DictionaryFormat::Tsv,
+        );
+
+        assert_eq!(dictionary.answers(), ["crane"]);
+        assert_eq!(dictionary.guesses(), ["crane", "aahed"]);
+        assert_eq!(
+            dictionary.skipped[0].reason,
+            SkipReason::IllegalKind("maybe".into())
+        );
+    }
+
+    #[test]
+    fn merging_appends_new_words_in_order() {
+        let mut dictionary = parse("crane\nslate\n", DictionaryFormat::Plain);
+
+        let added = dictionary.merge(parse("trace\ncrane\naudio\n", DictionaryFormat::Plain));
+
+        assert_eq!(added, 2);
+        assert_eq!(dictionary.guesses(), ["crane", "slate", "trace", "audio"]);
+    }
+
+    #[test]
+    fn merging_keeps_the_first_entry_of_a_duplicate() {
+        let mut dictionary = parse("crane 2\n", DictionaryFormat::Plain);
+
+        dictionary.merge(parse("crane 5\n", DictionaryFormat::Plain));
+
+        assert_eq!(dictionary.guesses(), ["crane"]);
+        assert_eq!(dictionary.priors().unwrap()["crane"], 2.0);
+    }
+
+    #[test]
+    fn merging_marks_answers_from_any_source() {
+        let mut dictionary = parse(
+            "word,kind\ncrane,guess\nslate,answer\n",
+            DictionaryFormat::Csv,
+        );
+
+        dictionary.merge(parse(
+            "word,kind\ncrane,answer\nslate,guess\n",
+            DictionaryFormat::Csv,
+        ));
+
+        assert_eq!(dictionary.answers(), ["crane", "slate"]);
+    }
+
+    #[test]
+    fn removing_drops_every_listed_word() {
+        let mut dictionary = parse("crane\nslate\ntrace\n", DictionaryFormat::Plain);
+
+        let removed = dictionary.remove(&parse("trace\ncrane\naudio\n", DictionaryFormat::Plain));
+
+        assert_eq!(removed, 2);
+        assert_eq!(dictionary.guesses(), ["slate"]);
+    }
+
+    #[test]
+    fn lines_that_are_not_utf8_are_skipped() {
+        let dictionary =
+            Dictionary::parse(&b"crane\r\nsl\xffte\nslate\n"[..], DictionaryFormat::Plain).unwrap();
+
+        assert_eq!(dictionary.answers(), ["crane", "slate"]);
+        assert_eq!(dictionary.skipped.len(), 1);
+        assert_eq!(dictionary.skipped[0].line, 2);
+        assert_eq!(dictionary.skipped[0].content, "sl\u{fffd}te");
+        assert_eq!(dictionary.skipped[0].reason, SkipReason::InvalidUtf8);
+    }
+
+    #[test]
+    fn unweighted_dictionaries_have_no_priors() {
+        assert!(parse("crane\nslate\n", DictionaryFormat::Plain)
+            .priors()
+            .is_none());
+    }
+}
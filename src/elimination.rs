@@ -0,0 +1,57 @@
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+
+/// The word picker rule that removed a word, with the letter and position it checked
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub enum Rule {
+    /// [remove_words](crate::HardModeWordPicker::remove_words), for reasons outside the game
+    Excluded,
+    /// [remove_words_containing](crate::HardModeWordPicker::remove_words_containing)
+    Containing(char),
+    /// [remove_words_not_containing](crate::HardModeWordPicker::remove_words_not_containing)
+    NotContaining(char),
+    /// [remove_words_with_letter_in_position](crate::HardModeWordPicker::remove_words_with_letter_in_position)
+    WithLetterInPosition(char, usize),
+    /// [remove_words_without_letter_in_position](crate::HardModeWordPicker::remove_words_without_letter_in_position)
+    WithoutLetterInPosition(char, usize),
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Rule::Excluded => write!(f, "it was excluded (ie. an answer already used)"),
+            Rule::Containing(letter) => write!(f, "it contains {}", letter),
+            Rule::NotContaining(letter) => write!(f, "it does not contain {}", letter),
+            Rule::WithLetterInPosition(letter, position) => {
+                write!(f, "it has {} in position {}", letter, position + 1)
+            }
+            Rule::WithoutLetterInPosition(letter, position) => {
+                write!(
+                    f,
+                    "it does not have {} in position {}",
+                    letter,
+                    position + 1
+                )
+            }
+        }
+    }
+}
+
+/// Why and when a word stopped being a potential solution
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct Elimination {
+    /// The turn (from 1) whose reply removed the word, `None` if removed outside a turn
+    pub turn: Option<usize>,
+    pub rule: Rule,
+}
+
+impl Display for Elimination {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.turn, self.rule) {
+            (Some(turn), rule) => write!(f, "ruled out in turn {}: {}", turn, rule),
+            (None, Rule::Excluded) => write!(f, "ruled out before the game: {}", Rule::Excluded),
+            (None, rule) => write!(f, "ruled out by known constraints: {}", rule),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Word lists embedded at build time, chosen with the `embed-answers`, `embed-guesses` and `extra-lists` features.
+
+use crate::NUM_LETTERS;
+
+/// Words of exactly [NUM_LETTERS] lowercase ascii letters packed back to back
+#[derive(Debug, Copy, Clone)]
+pub struct PackedWordList {
+    name: &'static str,
+    bytes: &'static [u8],
+}
+
+impl PackedWordList {
+    /// `bytes` holds whole words only, a trailing partial word is ignored
+    pub const fn new(name: &'static str, bytes: &'static [u8]) -> Self {
+        Self { name, bytes }
+    }
+
+    /// Name of the list, `answers`, `guesses` or the file stem of an extra list
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len() / NUM_LETTERS
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> {
+        self.bytes
+            .chunks_exact(NUM_LETTERS)
+            .map(|word| std::str::from_utf8(word).expect("embedded words are ascii"))
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/embedded_word_lists.rs"));
+
+/// Every embedded list that has words
+pub fn embedded_word_lists() -> impl Iterator<Item = PackedWordList> {
+    [DEFAULT_WORD_LIST, GUESS_WORD_LIST]
+        .into_iter()
+        .chain(EXTRA_WORD_LISTS)
+        .filter(|list| !list.is_empty())
+}
+
+/// Find an embedded list by name
+pub fn embedded_word_list(name: &str) -> Option<PackedWordList> {
+    embedded_word_lists().find(|list| list.name() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packed_words_are_read_back() {
+        let list = PackedWordList::new("test", b"cranegeeseslat");
+
+        assert_eq!(list.name(), "test");
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.iter().collect::<Vec<_>>(), ["crane", "geese"]);
+        assert!(list.iter().any(|word| word == "geese"));
+        assert!(!list.iter().any(|word| word == "slate"));
+    }
+
+    #[test]
+    fn empty_lists_are_not_listed() {
+        let list = PackedWordList::new("test", b"");
+
+        assert!(list.is_empty());
+        assert!(embedded_word_lists().all(|list| !list.is_empty()));
+    }
+}
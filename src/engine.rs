@@ -1,93 +1,348 @@
 use std::cmp::Reverse;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::sync::Arc;
 
 use arrayvec::ArrayVec;
-use log::{debug, info, trace};
+use clap::ArgEnum;
+use log::{debug, trace};
+use wordle_helper::analysis::{analyze_game, TurnAnalysis};
+use wordle_helper::correction::{suggest_corrections, Correction};
+use wordle_helper::dictionary::{Priors, SourceSummary};
+use wordle_helper::evaluation::evaluate_guesses;
+use wordle_helper::game::{make_turn, Guess, Reply, Response, Turn};
+use wordle_helper::opening_book;
+use wordle_helper::query::Pattern;
+use wordle_helper::report::TurnReport;
+use wordle_helper::strategy::Strategy;
+use wordle_helper::word_picker::{HardModeWordPicker, ALPHABET, ALPHA_LEN};
+use wordle_helper::{MAX_GUESSES, NUM_LETTERS};
 
+use crate::openers::print_evaluations;
 use crate::parser::{Parser, ReadTurnFlags};
-use crate::word_picker::{HardModeWordPicker, ALPHABET, ALPHA_LEN};
-use crate::{DEFAULT_WORD_LIST, MAX_GUESSES};
 
 /// Maximum number of potential solution words to present after a turn
 const BEST_WORDS_LEN: usize = 10;
+/// Maximum number of reply corrections to offer when no words are left
+const MAX_CORRECTIONS: usize = 5;
 
 /// Flag for special exit input
 pub struct Exit;
 
+/// How the state after each turn is written to stdout
+#[derive(Eq, PartialEq, Debug, Copy, Clone, ArgEnum)]
+pub enum OutputFormat {
+    /// Human readable best guesses and letter frequencies
+    Text,
+    /// One JSON object per turn, prompts are moved to stderr
+    Json,
+}
+
 /// Manages the word picker and handles input
 pub struct Engine {
     word_list: Vec<String>,
     parser: Parser,
     show_frequency: bool,
+    format: OutputFormat,
+    /// Strategy whose next guess is recommended after each turn, if any
+    strategy: Option<Strategy>,
+    /// If the precomputed opening book applies to the dictionary
+    in_book: bool,
+    /// Prior weights from a weighted dictionary, ranks likelier words first
+    priors: Option<Arc<Priors>>,
+    /// Words that are never the solution, even though they can be guessed
+    excluded: HashSet<String>,
+    /// Where the dictionary words came from, reported once on start
+    sources: Vec<SourceSummary>,
+    /// A guess still waiting for its reply, after constraints were entered at the reply prompt
+    pending_guess: Option<Guess>,
 }
 
 impl Engine {
-    /// Create a new engine, can be given a path to a dictionary text file
-    pub fn new(dictionary_path: Option<PathBuf>, show_frequency: bool) -> Self {
-        debug!(
-            "initializing engine with {}",
-            if let Some(path) = &dictionary_path {
-                path.to_str().unwrap_or("<non-unicode dictionary path>")
-            } else {
-                "default dictionary"
-            }
-        );
-        let word_list: Vec<_> = if let Some(dictionary_path) = dictionary_path {
-            let file = File::open(dictionary_path).expect("unable to read dictionary");
-            let reader = BufReader::new(file);
-
-            let word_list: Vec<_> = reader.lines().flatten().collect();
-            info!("processed word list containing {} words", word_list.len());
-
-            word_list
-        } else {
-            DEFAULT_WORD_LIST.into_iter().map(|s| s.into()).collect()
-        };
-
+    /// Create a new engine playing against a dictionary
+    pub fn new(
+        word_list: Vec<String>,
+        show_frequency: bool,
+        format: OutputFormat,
+        strategy: Option<Strategy>,
+        priors: Option<Arc<Priors>>,
+    ) -> Self {
+        debug!("initializing engine with {} words", word_list.len());
+        // The book was computed without weights
+        let in_book = priors.is_none() && opening_book::applies_to(&word_list);
+        debug!("opening book applies to dictionary: {}", in_book);
         Self {
             word_list,
+            strategy,
+            in_book,
+            priors,
+            excluded: HashSet::new(),
+            sources: Vec::new(),
+            pending_guess: None,
             show_frequency,
-            parser: Parser::new(),
+            format,
+            parser: Parser::new(format == OutputFormat::Json),
         }
     }
 
+    /// Never offer these words as solutions (ie. answers the daily game already used)
+    pub fn with_excluded_answers(mut self, excluded: HashSet<String>) -> Self {
+        debug!("excluding {} past answers", excluded.len());
+        // The opening book assumes every word of the dictionary is a potential solution
+        self.in_book &= excluded.is_empty();
+        self.excluded = excluded;
+
+        self
+    }
+
+    /// Report how many words each dictionary source contributed when starting
+    pub fn with_sources(mut self, sources: Vec<SourceSummary>) -> Self {
+        self.sources = sources;
+
+        self
+    }
+
     /// Runs the engine, continuously reads input and present solutions until exiting
     pub fn start(&mut self) -> Result<!, Exit> {
         debug!("starting engine");
+        for source in &self.sources {
+            match self.format {
+                OutputFormat::Text => println!("Dictionary {}", source),
+                OutputFormat::Json => eprintln!("Dictionary {}", source),
+            }
+        }
         loop {
-            let mut word_picker = HardModeWordPicker::new(self.word_list.clone());
+            let mut word_picker =
+                HardModeWordPicker::new(self.word_list.clone()).with_audit_trail();
+            if let Some(priors) = &self.priors {
+                word_picker = word_picker.with_priors(Arc::clone(priors));
+            }
+            word_picker.remove_words(&self.excluded);
+            // The word picker before any turn, to replay corrected turns on
+            let mut start_picker = word_picker.clone();
+            let mut turns: Vec<Turn> = Vec::new();
+            self.pending_guess = None;
             trace!("created fresh word picker from dictionary");
 
-            println!(
+            let new_game = format!(
                 "\nStarting new game - {} Potential Solutions",
-                self.word_list.len()
+                word_picker.remaining()
             );
+            match self.format {
+                OutputFormat::Text => println!("{}", new_game),
+                OutputFormat::Json => eprintln!("{}", new_game),
+            }
+            // Once constraints are entered directly the turns no longer tell the whole game
+            let mut known = false;
+            if self.format == OutputFormat::Text {
+                self.print_recommended_guess(&word_picker, &turns, self.in_book);
+            }
 
-            for turn_index in 0..MAX_GUESSES {
-                trace!("starting new turn {}", turn_index);
-                let turn = match self.parser.read_turn() {
-                    Ok(turn) => Ok(turn),
-                    Err(err) => match err {
-                        ReadTurnFlags::Exit(e) => Err(e),
-                        ReadTurnFlags::Win => break,
-                    },
-                }?;
-                // Remove words from word picker based on turn
-                word_picker.take_turn(turn);
-                self.print_best_guesses(&word_picker);
+            while turns.len() < MAX_GUESSES {
+                trace!("starting new turn {}", turns.len());
+                match self.read_turn(&word_picker) {
+                    Ok(turn) => {
+                        // Remove words from word picker based on turn
+                        turns.push(turn.clone());
+                        word_picker.take_turn(turn);
+                    }
+                    Err(ReadTurnFlags::Known(constraints)) => {
+                        // Remove words the same way, without taking a turn
+                        word_picker.apply_constraints(&constraints);
+                        start_picker.apply_constraints(&constraints);
+                        known = true;
+                    }
+                    Err(ReadTurnFlags::Exit(e)) => return Err(e),
+                    Err(
+                        ReadTurnFlags::Query(_) | ReadTurnFlags::Eval(_) | ReadTurnFlags::Why(_),
+                    ) => {
+                        unreachable!(
+                            "queries, evaluations and explanations are answered while reading"
+                        )
+                    }
+                    Err(ReadTurnFlags::Win(winning_guess)) => {
+                        if let Some(guess) = winning_guess {
+                            let response: Response = [Reply::Success; NUM_LETTERS].into();
+                            turns.push(make_turn(&guess, &response));
+                        }
+                        if !known {
+                            self.offer_analysis(&start_picker, &turns)?;
+                        }
+                        break;
+                    }
+                }
+                if word_picker.remaining() == 0 {
+                    if let Some(correction) = self.offer_corrections(&start_picker, &turns)? {
+                        word_picker = correction.replay(&start_picker);
+                        turns = correction.turns;
+                    }
+                }
+                match self.format {
+                    OutputFormat::Text => self.print_best_guesses(&word_picker),
+                    OutputFormat::Json => self.print_turn_report(turns.len(), &word_picker),
+                }
 
                 // If no words are left the game is scratch (incorrect dictionary or invalid user input)
                 if word_picker.remaining() == 0 {
                     break;
                 }
 
-                if self.show_frequency {
+                if self.show_frequency && self.format == OutputFormat::Text {
                     self.print_letter_frequencies(&word_picker);
                 }
+                // The reply to the pending guess is asked for next
+                if self.format == OutputFormat::Text && self.pending_guess.is_none() {
+                    self.print_recommended_guess(&word_picker, &turns, self.in_book && !known);
+                }
+            }
+        }
+    }
+
+    /// Read the next turn, answering any pattern queries, evaluations and explanations entered in the meantime
+    fn read_turn(&mut self, word_picker: &HardModeWordPicker) -> Result<Turn, ReadTurnFlags> {
+        let guess = match self.pending_guess.take() {
+            Some(guess) => guess,
+            None => self.read_answering_commands(word_picker, Parser::read_guess)?,
+        };
+        // Commands entered at the reply prompt are answered without forgetting the guess
+        let response = match self.read_answering_commands(word_picker, Parser::read_reply) {
+            Err(ReadTurnFlags::Win(None)) => return Err(ReadTurnFlags::Win(Some(guess))),
+            Err(ReadTurnFlags::Known(constraints)) => {
+                // Applied by the caller, then the reply to the same guess is asked for again
+                self.pending_guess = Some(guess);
+                return Err(ReadTurnFlags::Known(constraints));
+            }
+            response => response?,
+        };
+
+        Ok(make_turn(&guess, &response))
+    }
+
+    /// Read an input, answering any query, evaluation or explanation entered instead and asking again
+    fn read_answering_commands<T>(
+        &mut self,
+        word_picker: &HardModeWordPicker,
+        read: fn(&mut Parser) -> Result<T, ReadTurnFlags>,
+    ) -> Result<T, ReadTurnFlags> {
+        loop {
+            match read(&mut self.parser) {
+                Err(ReadTurnFlags::Query(pattern)) => self.print_query(word_picker, &pattern),
+                Err(ReadTurnFlags::Eval(words)) => self.print_evaluations(word_picker, words),
+                Err(ReadTurnFlags::Why(word)) => self.print_elimination(word_picker, &word),
+                result => break result,
+            }
+        }
+    }
+
+    /// Print the remaining words that match a pattern
+    fn print_query(&self, word_picker: &HardModeWordPicker, pattern: &Pattern) {
+        let matches = pattern.matches(word_picker.remaining_words());
+        let message = format!("{} Matches: {}", matches.len(), matches.join(", "));
+        match self.format {
+            OutputFormat::Text => println!("{}", message),
+            OutputFormat::Json => eprintln!("{}", message),
+        }
+    }
+
+    /// Print how well each word splits the remaining words, next to the best guess for comparison
+    fn print_evaluations(&self, word_picker: &HardModeWordPicker, mut words: Vec<String>) {
+        if let Some(best) = word_picker.top_10_words().next() {
+            if !words.contains(best) {
+                words.push(best.clone());
+            }
+        }
+        let evaluations =
+            evaluate_guesses(&words, word_picker.remaining_words(), word_picker.priors());
+        match self.format {
+            OutputFormat::Text => print_evaluations(&evaluations),
+            OutputFormat::Json => {
+                println!("{}", serde_json::json!({ "evaluations": evaluations }))
+            }
+        }
+    }
+
+    /// Print why a word is no longer a potential solution
+    fn print_elimination(&self, word_picker: &HardModeWordPicker, word: &str) {
+        let message = if let Some(elimination) = word_picker.elimination(word) {
+            format!("{} was {}", word, elimination)
+        } else if word_picker
+            .remaining_words()
+            .iter()
+            .any(|remaining| remaining == word)
+        {
+            format!("{} is still a potential solution", word)
+        } else {
+            format!("{} is not in the dictionary", word)
+        };
+        match self.format {
+            OutputFormat::Text => println!("{}", message),
+            OutputFormat::Json => eprintln!("{}", message),
+        }
+    }
+
+    /// When no words are left, offer the smallest changes to the entered replies that leave some (likely typos)
+    fn offer_corrections(
+        &mut self,
+        word_picker: &HardModeWordPicker,
+        turns: &[Turn],
+    ) -> Result<Option<Correction>, Exit> {
+        let mut corrections = suggest_corrections(word_picker, turns, MAX_CORRECTIONS);
+        if corrections.is_empty() {
+            return Ok(None);
+        }
+
+        let mut message = String::from("No words left, a reply may have been mistyped:");
+        for (index, correction) in corrections.iter().enumerate() {
+            message.push_str(&format!("\n{:>3}. {}", index + 1, correction));
+        }
+        match self.format {
+            OutputFormat::Text => println!("{}", message),
+            OutputFormat::Json => eprintln!("{}", message),
+        }
+
+        match self.parser.read_choice(
+            "apply a correction, anything else restarts",
+            corrections.len(),
+        ) {
+            Ok(choice) => Ok(choice.map(|index| corrections.swap_remove(index))),
+            Err(ReadTurnFlags::Exit(exit)) => Err(exit),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// After a win, rate each guess against the best guess available at the time
+    fn offer_analysis(
+        &mut self,
+        start_picker: &HardModeWordPicker,
+        turns: &[Turn],
+    ) -> Result<(), Exit> {
+        if turns.is_empty() {
+            return Ok(());
+        }
+
+        match self.format {
+            OutputFormat::Json => {
+                let analysis = analyze_game(start_picker, turns);
+                println!("{}", serde_json::json!({ "analysis": analysis }));
+            }
+            OutputFormat::Text => {
+                let show = match self.parser.read_confirmation("show analysis of this game?") {
+                    Ok(show) => show,
+                    Err(ReadTurnFlags::Exit(exit)) => return Err(exit),
+                    Err(
+                        ReadTurnFlags::Win(_)
+                        | ReadTurnFlags::Query(_)
+                        | ReadTurnFlags::Known(_)
+                        | ReadTurnFlags::Eval(_)
+                        | ReadTurnFlags::Why(_),
+                    ) => false,
+                };
+                if show {
+                    print_analysis(&analyze_game(start_picker, turns));
+                }
             }
         }
+
+        Ok(())
     }
 
     /// Print the top [BEST_WORDS_LEN] guesses
@@ -96,10 +351,16 @@ impl Engine {
 
         let remaining = word_picker.remaining();
         if remaining > 0 {
-            // Print out best guesses
+            // Print out best guesses, with their chance of being the solution when weighted
             let best_guesses = word_picker
                 .top_10_words()
-                .cloned()
+                .map(|word| {
+                    if word_picker.has_priors() {
+                        format!("{} ({:.1}%)", word, 100.0 * word_picker.probability(word))
+                    } else {
+                        word.clone()
+                    }
+                })
                 .collect::<ArrayVec<_, 10>>()
                 .join(BEST_GUESS_SEPARATOR);
 
@@ -114,6 +375,31 @@ impl Engine {
         }
     }
 
+    /// Print the chosen strategy's next guess, the first two come from the opening book when it applies
+    fn print_recommended_guess(
+        &self,
+        word_picker: &HardModeWordPicker,
+        turns: &[Turn],
+        in_book: bool,
+    ) {
+        let strategy = match self.strategy {
+            Some(strategy) => strategy,
+            None => return,
+        };
+        if let Some(guess) = opening_book::next_guess(strategy, in_book, word_picker, turns) {
+            println!("Recommended Guess ({}): {}", strategy, guess);
+        }
+    }
+
+    /// Print the state after a turn as a single line of JSON
+    fn print_turn_report(&self, turn: usize, word_picker: &HardModeWordPicker) {
+        let report = TurnReport::new(turn, word_picker);
+        println!(
+            "{}",
+            serde_json::to_string(&report).expect("turn report is always serializable")
+        );
+    }
+
     /// Print how many remaining words contain any given letter
     fn print_letter_frequencies(&self, word_picker: &HardModeWordPicker) {
         let mut letter_frequencies = ALPHABET
@@ -133,3 +419,28 @@ impl Engine {
         println!("Frequencies: {}", letter_frequencies);
     }
 }
+
+/// Print a table comparing each guess to the best guess at the time
+fn print_analysis(analysis: &[TurnAnalysis]) {
+    println!(
+        "{:>4}  {:5}  {:5}  {:>11}  {:>13}  {:>5}  {:>6}",
+        "turn", "guess", "best", "bits", "remaining", "skill", "luck"
+    );
+    for (index, turn) in analysis.iter().enumerate() {
+        println!(
+            "{:>4}  {:5}  {:5}  {:>5.2}/{:<5.2}  {:>5} -> {:<4}  {:>4.0}%  {:>+6.2}",
+            index + 1,
+            turn.guess,
+            turn.best_guess,
+            turn.guess_entropy,
+            turn.best_entropy,
+            turn.remaining_before,
+            turn.remaining_after,
+            turn.skill,
+            turn.luck
+        );
+    }
+    let skill = analysis.iter().map(|turn| turn.skill).sum::<f64>() / analysis.len() as f64;
+    let luck = analysis.iter().map(|turn| turn.luck).sum::<f64>();
+    println!("Average skill {:.0}%, total luck {:+.2} bits", skill, luck);
+}
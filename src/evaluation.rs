@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::dictionary::{Priors, DEFAULT_WEIGHT};
+use crate::game::{response_for, Response};
+
+/// How well a guess splits a set of candidate solutions by the reply it would receive
+#[derive(Debug, Clone, Serialize)]
+pub struct GuessEvaluation {
+    pub guess: String,
+    /// Number of distinct reply patterns the guess can receive
+    pub buckets: usize,
+    /// Most candidates sharing a single reply pattern (worst case remaining)
+    pub largest_bucket: usize,
+    /// Average number of candidates remaining after the guess (weighing replies by how likely they are)
+    pub expected_remaining: f64,
+    /// Expected information gained by the guess, in bits
+    pub entropy: f64,
+    /// If the guess is itself one of the candidates
+    pub possible_answer: bool,
+}
+
+impl GuessEvaluation {
+    pub fn new<S: AsRef<str>>(guess: &str, candidates: &[S]) -> Self {
+        Self::weighted(guess, candidates, None)
+    }
+
+    /// Weigh each candidate by its prior probability of being the solution, candidates missing from `priors` weigh [DEFAULT_WEIGHT]
+    ///
+    /// Without priors (or if every weight is zero) all candidates are equally likely.
+    pub fn weighted<S: AsRef<str>>(guess: &str, candidates: &[S], priors: Option<&Priors>) -> Self {
+        let weight = |word: &str| {
+            priors
+                .and_then(|priors| priors.get(word).copied())
+                .unwrap_or(DEFAULT_WEIGHT)
+        };
+        let total: f64 = candidates
+            .iter()
+            .map(|candidate| weight(candidate.as_ref()))
+            .sum();
+        if priors.is_some() && total <= 0.0 {
+            return Self::new(guess, candidates);
+        }
+
+        // Number of candidates and their weight for each reply
+        let mut buckets: HashMap<Response, (usize, f64)> = HashMap::new();
+        for candidate in candidates {
+            let bucket = buckets
+                .entry(response_for(guess, candidate.as_ref()))
+                .or_default();
+            bucket.0 += 1;
+            bucket.1 += weight(candidate.as_ref());
+        }
+        // Summed lightest first so the result does not depend on hash map order (build.rs sums the same way)
+        let mut buckets: Vec<_> = buckets.into_values().collect();
+        buckets.sort_unstable_by(|(a_size, a_mass), (b_size, b_mass)| {
+            a_mass.total_cmp(b_mass).then(a_size.cmp(b_size))
+        });
+        let (expected_remaining, entropy) = buckets.iter().filter(|&&(_, mass)| mass > 0.0).fold(
+            (0.0, 0.0),
+            |(expected_remaining, entropy), &(size, mass)| {
+                let probability = mass / total;
+                (
+                    expected_remaining + probability * size as f64,
+                    entropy - probability * probability.log2(),
+                )
+            },
+        );
+
+        Self {
+            guess: guess.to_string(),
+            buckets: buckets.len(),
+            largest_bucket: buckets.iter().map(|&(size, _)| size).max().unwrap_or(0),
+            expected_remaining,
+            entropy,
+            possible_answer: candidates.iter().any(|word| word.as_ref() == guess),
+        }
+    }
+}
+
+/// Count how many candidates would give each reply to a guess
+pub fn bucket_sizes<S: AsRef<str>>(guess: &str, candidates: &[S]) -> HashMap<Response, usize> {
+    let mut buckets = HashMap::new();
+    for candidate in candidates {
+        *buckets
+            .entry(response_for(guess, candidate.as_ref()))
+            .or_default() += 1;
+    }
+
+    buckets
+}
+
+/// Evaluate every guess against the same candidates, in the order of `guesses`, weighted by `priors` if given
+pub fn evaluate_guesses<G: AsRef<str>, S: AsRef<str>>(
+    guesses: &[G],
+    candidates: &[S],
+    priors: Option<&Priors>,
+) -> Vec<GuessEvaluation> {
+    guesses
+        .iter()
+        .map(|guess| GuessEvaluation::weighted(guess.as_ref(), candidates, priors))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn priors(weights: &[(&str, f64)]) -> Priors {
+        weights
+            .iter()
+            .map(|&(word, weight)| (word.to_string(), weight))
+            .collect()
+    }
+
+    #[test]
+    fn likely_candidates_weigh_more() {
+        let candidates = ["crane", "crate", "fuzzy"];
+        let even = GuessEvaluation::new("crane", &candidates);
+        let priors = priors(&[("crane", 1.0), ("crate", 1.0), ("fuzzy", 98.0)]);
+        let weighted = GuessEvaluation::weighted("crane", &candidates, Some(&priors));
+
+        assert_eq!(weighted.buckets, even.buckets);
+        assert_eq!(weighted.largest_bucket, even.largest_bucket);
+        assert!((even.entropy - 3f64.log2()).abs() < 1e-9);
+        assert!(weighted.entropy < 0.2);
+        assert!((weighted.expected_remaining - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn zero_weights_fall_back_to_even_odds() {
+        let candidates = ["crane", "crate", "fuzzy"];
+        let even = GuessEvaluation::new("fuzzy", &candidates);
+        let priors = priors(&[("crane", 0.0), ("crate", 0.0), ("fuzzy", 0.0)]);
+        let weighted = GuessEvaluation::weighted("fuzzy", &candidates, Some(&priors));
+
+        assert_eq!(weighted.entropy, even.entropy);
+        assert_eq!(weighted.expected_remaining, even.expected_remaining);
+    }
+}
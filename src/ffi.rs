@@ -0,0 +1,324 @@
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::ptr;
+use std::slice;
+use std::sync::Arc;
+
+use crate::dictionary::Priors;
+use crate::report::suggestions;
+use crate::solver::{parse_turn, Solver, SolverError};
+use crate::{default_word_list, NUM_LETTERS};
+
+/// Outcome of a fallible call
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WordleStatus {
+    Ok = 0,
+    /// A required pointer argument was null
+    NullPointer,
+    /// A string argument was not valid UTF-8
+    InvalidUtf8,
+    /// Guess is not a word of `WORDLE_NUM_LETTERS` ascii letters
+    InvalidWord,
+    /// Reply is not made of `WORDLE_NUM_LETTERS` of `+` (hit), `-` (partial) and `.` (miss)
+    InvalidReply,
+    /// Turn leaves no potential solutions, the solver is left unchanged
+    ContradictoryTurn,
+    /// There is no turn to undo
+    NothingToUndo,
+}
+
+impl From<SolverError> for WordleStatus {
+    fn from(error: SolverError) -> Self {
+        match error {
+            SolverError::InvalidWord(_) => WordleStatus::InvalidWord,
+            SolverError::InvalidReply(_) => WordleStatus::InvalidReply,
+            SolverError::ContradictoryTurn => WordleStatus::ContradictoryTurn,
+            SolverError::NothingToUndo => WordleStatus::NothingToUndo,
+        }
+    }
+}
+
+/// Word length for guesses and the dictionary
+// Written out so cbindgen can resolve it without parsing the rest of the crate
+pub const WORDLE_NUM_LETTERS: usize = 5;
+const _: () = assert!(WORDLE_NUM_LETTERS == NUM_LETTERS);
+/// Buffer size for a word including its nul terminator
+pub const WORDLE_WORD_SIZE: usize = WORDLE_NUM_LETTERS + 1;
+
+/// A ranked guess, `word` is nul terminated
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct WordleSuggestion {
+    pub word: [c_char; WORDLE_WORD_SIZE],
+    /// Number of distinct letters in the word
+    pub score: usize,
+    /// Chance of the word being the solution, suggestions are ranked by it when the solver has weights
+    pub probability: f64,
+}
+
+/// Opaque solver handle
+pub struct WordleSolver {
+    solver: Solver,
+}
+
+/// Create a solver from `len` nul terminated words, or from the embedded dictionary when `words` is null
+///
+/// Words that are not valid UTF-8 are skipped. Returns null if any word pointer is null,
+/// or if `words` is null and the library was built without an embedded dictionary (`embed-answers`).
+/// The solver must be released with `wordle_solver_free`.
+///
+/// # Safety
+///
+/// `words` must be null or point to `len` pointers to nul terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_new(
+    words: *const *const c_char,
+    len: usize,
+) -> *mut WordleSolver {
+    let word_list = if words.is_null() {
+        let word_list = default_word_list();
+        if word_list.is_empty() {
+            return ptr::null_mut();
+        }
+        word_list
+    } else {
+        let mut word_list = Vec::with_capacity(len);
+        for &word in slice::from_raw_parts(words, len) {
+            if word.is_null() {
+                return ptr::null_mut();
+            }
+            if let Ok(word) = CStr::from_ptr(word).to_str() {
+                word_list.push(word.to_string());
+            }
+        }
+        word_list
+    };
+
+    Box::into_raw(Box::new(WordleSolver {
+        solver: Solver::new(word_list),
+    }))
+}
+
+/// Create a solver from `len` nul terminated words where `weights[i]` is the prior probability of `words[i]`
+///
+/// Words that are not valid UTF-8 are skipped. Returns null if any pointer is null.
+/// The solver must be released with `wordle_solver_free`.
+///
+/// # Safety
+///
+/// `words` must point to `len` pointers to nul terminated strings and `weights` to `len` weights.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_new_weighted(
+    words: *const *const c_char,
+    weights: *const f64,
+    len: usize,
+) -> *mut WordleSolver {
+    if words.is_null() || weights.is_null() {
+        return ptr::null_mut();
+    }
+    let mut word_list = Vec::with_capacity(len);
+    let mut priors = Priors::with_capacity(len);
+    for (&word, &weight) in slice::from_raw_parts(words, len)
+        .iter()
+        .zip(slice::from_raw_parts(weights, len))
+    {
+        if word.is_null() {
+            return ptr::null_mut();
+        }
+        if let Ok(word) = CStr::from_ptr(word).to_str() {
+            word_list.push(word.to_string());
+            priors.insert(word.to_string(), weight);
+        }
+    }
+
+    Box::into_raw(Box::new(WordleSolver {
+        solver: Solver::new(word_list).with_priors(Arc::new(priors)),
+    }))
+}
+
+/// Release a solver, null is ignored
+///
+/// # Safety
+///
+/// `solver` must be null or a pointer returned by `wordle_solver_new` that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_free(solver: *mut WordleSolver) {
+    if !solver.is_null() {
+        drop(Box::from_raw(solver));
+    }
+}
+
+/// Apply a guess (ie. `crane`) and its reply (ie. `.-..+`)
+///
+/// # Safety
+///
+/// `solver` must be a live solver, `guess` and `reply` must be nul terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_apply_turn(
+    solver: *mut WordleSolver,
+    guess: *const c_char,
+    reply: *const c_char,
+) -> WordleStatus {
+    if solver.is_null() || guess.is_null() || reply.is_null() {
+        return WordleStatus::NullPointer;
+    }
+    let (guess, reply) = match (
+        CStr::from_ptr(guess).to_str(),
+        CStr::from_ptr(reply).to_str(),
+    ) {
+        (Ok(guess), Ok(reply)) => (guess, reply),
+        _ => return WordleStatus::InvalidUtf8,
+    };
+
+    match parse_turn(guess, reply).and_then(|turn| (*solver).solver.apply_turn(turn)) {
+        Ok(()) => WordleStatus::Ok,
+        Err(error) => error.into(),
+    }
+}
+
+/// Remove the last turn
+///
+/// # Safety
+///
+/// `solver` must be a live solver.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_undo(solver: *mut WordleSolver) -> WordleStatus {
+    if solver.is_null() {
+        return WordleStatus::NullPointer;
+    }
+
+    match (*solver).solver.undo() {
+        Ok(_) => WordleStatus::Ok,
+        Err(error) => error.into(),
+    }
+}
+
+/// Start over with every word in the dictionary
+///
+/// # Safety
+///
+/// `solver` must be a live solver.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_reset(solver: *mut WordleSolver) -> WordleStatus {
+    if solver.is_null() {
+        return WordleStatus::NullPointer;
+    }
+    (*solver).solver.reset();
+
+    WordleStatus::Ok
+}
+
+/// How many potential solutions remain, 0 for a null solver
+///
+/// # Safety
+///
+/// `solver` must be null or a live solver.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_remaining(solver: *const WordleSolver) -> usize {
+    if solver.is_null() {
+        return 0;
+    }
+
+    (*solver).solver.word_picker().remaining()
+}
+
+/// Write up to `capacity` ranked suggestions (best first) into `out`, returning how many were written
+///
+/// # Safety
+///
+/// `solver` must be a live solver and `out` must point to at least `capacity` suggestions.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_suggestions(
+    solver: *const WordleSolver,
+    out: *mut WordleSuggestion,
+    capacity: usize,
+) -> usize {
+    if solver.is_null() || out.is_null() {
+        return 0;
+    }
+    let out = slice::from_raw_parts_mut(out, capacity);
+    let suggestions = suggestions((*solver).solver.word_picker());
+
+    for (slot, suggestion) in out.iter_mut().zip(&suggestions) {
+        let mut word = [0; WORDLE_WORD_SIZE];
+        for (c, &byte) in word.iter_mut().zip(suggestion.word.as_bytes()) {
+            *c = byte as c_char;
+        }
+        *slot = WordleSuggestion {
+            word,
+            score: suggestion.score,
+            probability: suggestion.probability,
+        };
+    }
+
+    suggestions.len().min(capacity)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+
+    use super::*;
+
+    fn c_strings(strings: &[&str]) -> Vec<CString> {
+        strings
+            .iter()
+            .map(|string| CString::new(*string).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn solver_round_trip() {
+        let words = c_strings(&["crane", "crate", "grate", "fuzzy"]);
+        let pointers: Vec<_> = words.iter().map(|word| word.as_ptr()).collect();
+        let [fuzzy, grate, misses]: [CString; 3] =
+            c_strings(&["fuzzy", "grate", "....."]).try_into().unwrap();
+
+        unsafe {
+            let solver = wordle_solver_new(pointers.as_ptr(), pointers.len());
+            assert!(!solver.is_null());
+            assert_eq!(wordle_solver_remaining(solver), 4);
+
+            let status = wordle_solver_apply_turn(solver, fuzzy.as_ptr(), misses.as_ptr());
+            assert_eq!(status, WordleStatus::Ok);
+            assert_eq!(wordle_solver_remaining(solver), 3);
+            let status = wordle_solver_apply_turn(solver, grate.as_ptr(), misses.as_ptr());
+            assert_eq!(status, WordleStatus::ContradictoryTurn);
+            assert_eq!(wordle_solver_remaining(solver), 3);
+
+            let mut out = [WordleSuggestion {
+                word: [0; WORDLE_WORD_SIZE],
+                score: 0,
+                probability: 0.0,
+            }; 2];
+            assert_eq!(
+                wordle_solver_suggestions(solver, out.as_mut_ptr(), out.len()),
+                2
+            );
+            for suggestion in &out {
+                let word = CStr::from_ptr(suggestion.word.as_ptr()).to_str().unwrap();
+                assert!(["crane", "crate", "grate"].contains(&word));
+                assert_eq!(suggestion.probability, 1.0 / 3.0);
+            }
+
+            assert_eq!(wordle_solver_undo(solver), WordleStatus::Ok);
+            assert_eq!(wordle_solver_remaining(solver), 4);
+            assert_eq!(wordle_solver_undo(solver), WordleStatus::NothingToUndo);
+            wordle_solver_free(solver);
+        }
+    }
+
+    #[test]
+    fn null_pointers_are_rejected() {
+        unsafe {
+            assert!(wordle_solver_new_weighted(ptr::null(), ptr::null(), 0).is_null());
+            assert_eq!(
+                wordle_solver_undo(ptr::null_mut()),
+                WordleStatus::NullPointer
+            );
+            assert_eq!(wordle_solver_remaining(ptr::null()), 0);
+            wordle_solver_free(ptr::null_mut());
+        }
+    }
+}
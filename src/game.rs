@@ -1,4 +1,7 @@
 use arrayvec::{ArrayString, ArrayVec};
+use log::warn;
+
+use crate::word_picker::ALPHA_LEN;
 
 /// Word length for guessing and dictionary
 /// Effectively how many slots are there for letters to be guessed in.
@@ -11,8 +14,15 @@ pub const MAX_GUESSES: usize = 6;
 /// A special stack allocated string only holding 5 characters
 pub type Guess = ArrayString<NUM_LETTERS>;
 
+/// Symbol for a [Reply::Success] in text input and output
+pub const REPLY_SUCCESS: char = '+';
+/// Symbol for a [Reply::Miss] in text input and output
+pub const REPLY_MISS: char = '.';
+/// Symbol for a [Reply::Partial] in text input and output
+pub const REPLY_PARTIAL: char = '-';
+
 /// The answer given immediately after submitting a guess
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Reply {
     /// Letter matches exact position in solution (Green)
     Success,
@@ -25,9 +35,119 @@ pub enum Reply {
     Partial,
 }
 
+impl Reply {
+    /// Parse a single reply symbol
+    pub fn from_symbol(symbol: char) -> Option<Self> {
+        match symbol {
+            REPLY_SUCCESS => Some(Reply::Success),
+            REPLY_MISS => Some(Reply::Miss),
+            REPLY_PARTIAL => Some(Reply::Partial),
+            _ => None,
+        }
+    }
+
+    /// The symbol used to write this reply
+    pub fn symbol(self) -> char {
+        match self {
+            Reply::Success => REPLY_SUCCESS,
+            Reply::Miss => REPLY_MISS,
+            Reply::Partial => REPLY_PARTIAL,
+        }
+    }
+}
+
 /// The answers given for a submitted attempt
 pub type Response = ArrayVec<Reply, NUM_LETTERS>;
 
+/// Number of distinct responses, every slot can be any of the three replies
+pub const NUM_RESPONSES: usize = 3usize.pow(NUM_LETTERS as u32);
+
+/// Encode a response as a number below [NUM_RESPONSES] (base 3, first slot most significant)
+pub fn response_index(response: &Response) -> usize {
+    response.iter().fold(0, |index, reply| {
+        index * 3
+            + match reply {
+                Reply::Miss => 0,
+                Reply::Partial => 1,
+                Reply::Success => 2,
+            }
+    })
+}
+
+/// Parse a response written with reply symbols (ie. `+.-..`)
+pub fn parse_response(input: &str) -> Option<Response> {
+    if input.chars().count() != NUM_LETTERS {
+        return None;
+    }
+
+    input.chars().map(Reply::from_symbol).collect()
+}
+
+/// Write a response with reply symbols (ie. `+.-..`)
+pub fn format_response(response: &Response) -> String {
+    response.iter().map(|reply| reply.symbol()).collect()
+}
+
+/// The words that are [NUM_LETTERS] ascii letters, trimmed and lowercased so [response_for] accepts them
+///
+/// Anything else (blank lines, wrong lengths, punctuation) is left out with a warning.
+pub fn valid_words<S: AsRef<str>>(words: &[S]) -> Vec<String> {
+    let valid: Vec<_> = words
+        .iter()
+        .map(|word| word.as_ref().trim().to_ascii_lowercase())
+        .filter(|word| word.len() == NUM_LETTERS && word.bytes().all(|b| b.is_ascii_lowercase()))
+        .collect();
+    if valid.len() < words.len() {
+        warn!(
+            "left out {} words that are not {} letters",
+            words.len() - valid.len(),
+            NUM_LETTERS
+        );
+    }
+
+    valid
+}
+
+/// The response the game would give for a guess if `solution` was the answer
+///
+/// Exact matches are claimed first, then partials are given left to right while unmatched copies of the letter remain in the solution.
+pub fn response_for(guess: &str, solution: &str) -> Response {
+    debug_assert_eq!(
+        guess.len(),
+        NUM_LETTERS,
+        "guess must be {} letters",
+        NUM_LETTERS
+    );
+    debug_assert_eq!(
+        solution.len(),
+        NUM_LETTERS,
+        "solution must be {} letters",
+        NUM_LETTERS
+    );
+    let guess = guess.as_bytes();
+    let solution = solution.as_bytes();
+    let mut response: Response = [Reply::Miss; NUM_LETTERS].into();
+    // Count the solution letters that weren't matched exactly
+    let mut unmatched = [0u8; ALPHA_LEN];
+
+    for index in 0..NUM_LETTERS {
+        if guess[index] == solution[index] {
+            response[index] = Reply::Success;
+        } else {
+            unmatched[(solution[index] - b'a') as usize] += 1;
+        }
+    }
+    for index in 0..NUM_LETTERS {
+        let letter_index = (guess[index] - b'a') as usize;
+        if response[index] != Reply::Success && unmatched[letter_index] > 0 {
+            unmatched[letter_index] -= 1;
+            response[index] = Reply::Partial;
+        }
+    }
+
+    response
+}
+
 /// A single cell (letter) after a guess has been replied to
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub struct GameCell {
@@ -37,3 +157,73 @@ pub struct GameCell {
 
 /// The guess and solution zipped by slot
 pub type Turn = ArrayVec<GameCell, NUM_LETTERS>;
+
+/// Zip a guess with its response
+pub fn make_turn(guess: &str, response: &Response) -> Turn {
+    guess
+        .chars()
+        .zip(response.iter())
+        .map(|(letter, &reply)| GameCell { letter, reply })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reply(guess: &str, solution: &str) -> String {
+        format_response(&response_for(guess, solution))
+    }
+
+    #[test]
+    fn replies_mark_hits_partials_and_misses() {
+        assert_eq!(reply("crane", "crane"), "+++++");
+        assert_eq!(reply("crane", "react"), "--+.-");
+        assert_eq!(reply("fuzzy", "crane"), ".....");
+    }
+
+    #[test]
+    fn repeated_letters_only_count_the_copies_in_the_solution() {
+        // One `e` in the solution, the exact match claims it before the partial
+        assert_eq!(reply("geese", "crane"), "....+");
+        // Partials are handed out left to right
+        assert_eq!(reply("eerie", "ocean"), "-....");
+        assert_eq!(reply("speed", "abide"), "..-.-");
+        assert_eq!(reply("mamma", "madam"), "++-.-");
+    }
+
+    #[test]
+    fn responses_round_trip_through_their_symbols() {
+        let response = parse_response("+.-..").unwrap();
+
+        assert_eq!(
+            response.as_slice(),
+            [
+                Reply::Success,
+                Reply::Miss,
+                Reply::Partial,
+                Reply::Miss,
+                Reply::Miss
+            ]
+        );
+        assert_eq!(format_response(&response), "+.-..");
+    }
+
+    #[test]
+    fn malformed_responses_are_rejected() {
+        assert_eq!(parse_response("+.-."), None);
+        assert_eq!(parse_response("+.-..."), None);
+        assert_eq!(parse_response("+.x.."), None);
+        assert_eq!(parse_response(""), None);
+    }
+
+    #[test]
+    fn response_indexes_are_distinct() {
+        assert_eq!(response_index(&parse_response(".....").unwrap()), 0);
+        assert_eq!(
+            response_index(&parse_response("+++++").unwrap()),
+            NUM_RESPONSES - 1
+        );
+        assert_eq!(response_index(&parse_response("....-").unwrap()), 1);
+    }
+}
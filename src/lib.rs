@@ -0,0 +1,36 @@
+//! Wordle solver core: the game model, a hard-mode word picker and a solver with turn history.
+//!
+//! Nothing in this crate reads stdin or writes stdout, front ends drive a [Solver] (or a [HardModeWordPicker] directly) with [Turn]s.
+
+pub use crate::dictionary::{default_word_list, load_word_list};
+pub use crate::embedded::DEFAULT_WORD_LIST;
+pub use crate::evaluation::GuessEvaluation;
+pub use crate::game::{GameCell, Guess, Reply, Response, Turn, MAX_GUESSES, NUM_LETTERS};
+pub use crate::solver::{Solver, SolverError};
+pub use crate::word_picker::HardModeWordPicker;
+
+pub mod analysis;
+pub mod constraints;
+pub mod correction;
+pub mod decision_tree;
+pub mod dictionary;
+pub mod elimination;
+pub mod embedded;
+pub mod evaluation;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod game;
+pub mod lint;
+pub mod opening;
+pub mod opening_book;
+pub mod past_answers;
+pub mod query;
+pub mod report;
+pub mod simulation;
+pub mod solver;
+pub mod stats;
+pub mod strategy;
+pub mod tournament;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
+pub mod word_picker;
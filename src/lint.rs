@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::dictionary::{open_dictionary, Dictionary, DictionaryFormat, SkipReason};
+
+/// Something wrong with a dictionary line
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    /// The line is dropped when loading the dictionary
+    Skipped(SkipReason),
+    /// The word is loaded, but lowercased
+    MixedCase,
+    /// The line ends with whitespace
+    TrailingWhitespace,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::Skipped(reason) => write!(f, "dropped, {}", reason),
+            Problem::MixedCase => f.write_str("word is not lowercase"),
+            Problem::TrailingWhitespace => f.write_str("trailing whitespace"),
+        }
+    }
+}
+
+/// A problem found on a dictionary line
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    /// One-based line number in the file
+    pub line: usize,
+    pub content: String,
+    pub problem: Problem,
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: {} ({:?})",
+            self.line, self.problem, self.content
+        )
+    }
+}
+
+/// Result of checking a dictionary file
+#[derive(Debug, Clone)]
+pub struct DictionaryCheck {
+    pub dictionary: Dictionary,
+    /// Every problem found, by line
+    pub issues: Vec<Issue>,
+}
+
+impl DictionaryCheck {
+    /// If no problems were found
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Check every line of a dictionary file, including those that load fine but are sloppy
+pub fn check_dictionary<P: AsRef<Path>>(dictionary_path: P) -> io::Result<DictionaryCheck> {
+    let dictionary_path = dictionary_path.as_ref();
+    let format = DictionaryFormat::from_path(dictionary_path);
+    let mut data = Vec::new();
+    open_dictionary(dictionary_path)?.read_to_end(&mut data)?;
+
+    check_data(&data, format)
+}
+
+/// Check every line of a dictionary already read into memory
+pub fn check_data(data: &[u8], format: DictionaryFormat) -> io::Result<DictionaryCheck> {
+    let dictionary = Dictionary::parse(data, format)?;
+    // Lines that are not UTF-8 are reported as skipped, showing them lossily is enough
+    let text = String::from_utf8_lossy(data);
+    let lines: Vec<_> = text.lines().collect();
+
+    let mut issues = BTreeMap::new();
+    let mut report = |line: usize, problem: Problem| {
+        issues.entry(line).or_insert_with(Vec::new).push(Issue {
+            line,
+            content: lines[line - 1].to_string(),
+            problem,
+        })
+    };
+
+    for skipped in &dictionary.skipped {
+        report(skipped.line, Problem::Skipped(skipped.reason.clone()));
+    }
+    for entry in dictionary.entries.iter().filter(|entry| entry.mixed_case) {
+        report(entry.line, Problem::MixedCase);
+    }
+    for (index, content) in lines.iter().enumerate() {
+        // Trailing tabs are empty columns in a tsv dictionary
+        let trimmed = match format {
+            DictionaryFormat::Tsv => {
+                content.trim_end_matches(|c: char| c.is_whitespace() && c != '\t')
+            }
+            _ => content.trim_end(),
+        };
+        if trimmed.len() != content.len() {
+            report(index + 1, Problem::TrailingWhitespace);
+        }
+    }
+
+    Ok(DictionaryCheck {
+        dictionary,
+        issues: issues.into_values().flatten().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(data: &str, format: DictionaryFormat) -> Vec<(usize, Problem)> {
+        check_data(data.as_bytes(), format)
+            .unwrap()
+            .issues
+            .into_iter()
+            .map(|issue| (issue.line, issue.problem))
+            .collect()
+    }
+
+    #[test]
+    fn clean_dictionaries_have_no_issues() {
+        let check = check_data(b"crane\n# comment\n\nslate 2\n", DictionaryFormat::Plain).unwrap();
+
+        assert!(check.is_clean());
+        assert_eq!(check.dictionary.entries.len(), 2);
+    }
+
+    #[test]
+    fn mixed_case_is_found_on_its_own_line() {
+        // The lowercase word also appears elsewhere in the file, and in the comment of its own line
+        let data = "crane\nCrane # crane\nSLATE\n";
+
+        assert_eq!(
+            problems(data, DictionaryFormat::Plain),
+            [
+                (2, Problem::Skipped(SkipReason::Duplicate(1))),
+                (3, Problem::MixedCase)
+            ]
+        );
+    }
+
+    #[test]
+    fn mixed_case_is_found_in_columns() {
+        // The lowercase word also appears in another column of the line
+        let data = "word,weight,tags\nslate,crane\nCrane,2,crane\n";
+
+        assert_eq!(
+            problems(data, DictionaryFormat::Csv),
+            [
+                (
+                    2,
+                    Problem::Skipped(SkipReason::IllegalWeight("crane".into()))
+                ),
+                (3, Problem::MixedCase)
+            ]
+        );
+    }
+
+    #[test]
+    fn trailing_whitespace_is_found() {
+        assert_eq!(
+            problems("crane \nslate\n", DictionaryFormat::Plain),
+            [(1, Problem::TrailingWhitespace)]
+        );
+        // Trailing tabs are empty columns
+        assert!(problems("crane\t2\t\n", DictionaryFormat::Tsv).is_empty());
+    }
+
+    #[test]
+    fn skipped_lines_are_reported() {
+        assert_eq!(
+            problems("cranes\ncr4ne\n", DictionaryFormat::Plain),
+            [
+                (1, Problem::Skipped(SkipReason::WrongLength(6))),
+                (2, Problem::Skipped(SkipReason::IllegalCharacter('4')))
+            ]
+        );
+    }
+}
@@ -1,31 +1,240 @@
 #![feature(never_type)]
 #![feature(stdio_locked)]
 
+use std::collections::HashSet;
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use clap::{ArgEnum, Parser};
+use clap::{ArgEnum, CommandFactory, ErrorKind, Parser, Subcommand};
 use log::{debug, LevelFilter};
 use simple_logger::SimpleLogger;
 
-use crate::default_word_list::DEFAULT_WORD_LIST;
-use crate::engine::Engine;
-use crate::game::{MAX_GUESSES, NUM_LETTERS};
+use wordle_helper::dictionary::{Dictionary, SourceKind, SourceSummary};
+use wordle_helper::embedded::{embedded_word_list, embedded_word_lists};
+use wordle_helper::evaluation::evaluate_guesses;
+use wordle_helper::opening::MAX_OPENING_LEN;
+use wordle_helper::past_answers::{is_iso_date, load_past_answers, used_answers};
+use wordle_helper::query::Pattern;
+use wordle_helper::solver::parse_guess;
+use wordle_helper::strategy::Strategy;
+use wordle_helper::tournament::Contestant;
+use wordle_helper::{load_word_list, DEFAULT_WORD_LIST};
 
-mod default_word_list;
+use crate::dict::{run_check, run_stats};
+use crate::engine::{Engine, OutputFormat};
+use crate::openers::{print_evaluations, run_openers, run_opening_search, OpenerOrder};
+use crate::rpc::RpcServer;
+use crate::server::HttpServer;
+use crate::simulate::{run_simulation, run_tournament};
+use crate::tree::{run_tree, TreeFormat};
+
+mod dict;
 mod engine;
-mod game;
+mod openers;
 mod parser;
-mod word_picker;
+mod rpc;
+mod server;
+mod simulate;
+mod tree;
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
-    #[clap(short, long, parse(from_os_str), value_name = "FILE")]
-    dictionary: Option<PathBuf>,
+    /// Dictionary to use instead of the embedded one: words (optionally followed by a weight), csv or tsv, gzip compressed or not
+    ///
+    /// Can be repeated, the dictionaries are merged in order and de-duplicated. `embedded:<name>` selects an embedded list.
+    #[clap(
+        short,
+        long = "dictionary",
+        parse(from_os_str),
+        value_name = "FILE",
+        multiple_occurrences = true
+    )]
+    dictionaries: Vec<PathBuf>,
+    /// Additional words to merge on top of the dictionaries (or the embedded one), can be repeated
+    #[clap(
+        long = "include",
+        parse(from_os_str),
+        value_name = "FILE",
+        multiple_occurrences = true
+    )]
+    includes: Vec<PathBuf>,
+    /// Words to remove entirely (ie. banned or offensive words), can be repeated
+    #[clap(
+        long = "exclude",
+        parse(from_os_str),
+        value_name = "FILE",
+        multiple_occurrences = true
+    )]
+    excludes: Vec<PathBuf>,
     #[clap(short, long, arg_enum, value_name = "LEVEL")]
     log_level: Option<MyLogLevel>,
     #[clap(long = "hide-freq")]
     hide_letter_frequency: bool,
+    #[clap(long, arg_enum, value_name = "FORMAT", default_value = "text")]
+    format: OutputFormat,
+    /// Answers the daily game already used (one per line, `word` or `YYYY-MM-DD word`), never offered as solutions
+    #[clap(long, parse(from_os_str), value_name = "FILE")]
+    past_answers: Option<PathBuf>,
+    /// Only exclude past answers used before this day (`YYYY-MM-DD`), to replay archive puzzles
+    ///
+    /// Past answers without a date are still excluded.
+    #[clap(long, value_name = "DATE", requires = "past-answers")]
+    before: Option<String>,
+    /// Recommend the next guess of this strategy after each turn (picker, letter-frequency, entropy, minimax)
+    #[clap(long, value_name = "STRATEGY")]
+    strategy: Option<Strategy>,
+    /// Speak newline-delimited JSON-RPC on stdin/stdout instead of prompting, cannot be combined with a subcommand
+    #[clap(long)]
+    rpc: bool,
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Serve the solver over HTTP
+    Serve {
+        /// Port to listen on
+        #[clap(short, long, default_value_t = 8080)]
+        port: u16,
+        /// Address to bind, keep it local unless the solver should be reachable from other machines
+        #[clap(long, default_value = "127.0.0.1")]
+        host: String,
+    },
+    /// Play the solver against every answer and report how many guesses it needed
+    Simulate {
+        /// Answers to play against (one per line), defaults to the dictionary
+        #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+        answers: Option<PathBuf>,
+        /// Only play an evenly spaced sample of this many answers
+        #[clap(short, long, value_name = "COUNT")]
+        sample: Option<usize>,
+        /// How many of the hardest answers to list
+        #[clap(short, long, default_value_t = 10, value_name = "COUNT")]
+        worst: usize,
+        /// How each guess is chosen (picker, letter-frequency, entropy, minimax)
+        #[clap(long, default_value = "picker", value_name = "STRATEGY")]
+        strategy: Strategy,
+        /// Always open with this word
+        #[clap(long, value_name = "WORD")]
+        start: Option<String>,
+    },
+    /// Play several strategies and/or starting words over the same answers and compare them
+    Tournament {
+        /// Answers to play against (one per line), defaults to the dictionary
+        #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+        answers: Option<PathBuf>,
+        /// Only play an evenly spaced sample of this many answers
+        #[clap(short, long, value_name = "COUNT")]
+        sample: Option<usize>,
+        /// Strategy to enter, can be repeated, defaults to every strategy
+        #[clap(
+            long = "strategy",
+            value_name = "STRATEGY",
+            multiple_occurrences = true
+        )]
+        strategies: Vec<Strategy>,
+        /// Starting word to enter with each strategy, can be repeated
+        #[clap(long = "start", value_name = "WORD", multiple_occurrences = true)]
+        starts: Vec<String>,
+        /// How many differing answers to list for each pair of contestants
+        #[clap(short, long, default_value_t = 10, value_name = "COUNT")]
+        list: usize,
+        /// Export guesses per answer and contestant as csv
+        #[clap(long, parse(from_os_str), value_name = "FILE")]
+        csv: Option<PathBuf>,
+    },
+    /// Score every word in the dictionary as a first guess against the answers
+    Openers {
+        /// Answers to score against (one per line), defaults to the dictionary
+        #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+        answers: Option<PathBuf>,
+        /// How to rank the openers
+        #[clap(long, arg_enum, default_value = "entropy", value_name = "ORDER")]
+        sort: OpenerOrder,
+        /// How many of the best openers to print
+        #[clap(short, long, default_value_t = 20, value_name = "COUNT")]
+        top: usize,
+        /// Export every opener, sorted, as csv
+        #[clap(long, parse(from_os_str), value_name = "FILE")]
+        csv: Option<PathBuf>,
+    },
+    /// Find the fixed pair or triple of opening guesses that together split the answers the most
+    Opening {
+        /// Answers to split (one per line), defaults to the dictionary
+        #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+        answers: Option<PathBuf>,
+        /// Number of words in the opening
+        #[clap(short, long, default_value_t = 2, value_name = "COUNT")]
+        length: usize,
+        /// Only combine this many of the best single openers
+        #[clap(short, long, default_value_t = 100, value_name = "COUNT")]
+        pool: usize,
+        /// How many of the best openings to print
+        #[clap(short, long, default_value_t = 10, value_name = "COUNT")]
+        top: usize,
+    },
+    /// Export the complete play tree of a strategy: guess, reply, next guess, until every word is solved
+    Tree {
+        /// How each guess is chosen (picker, letter-frequency, entropy, minimax)
+        #[clap(long, default_value = "entropy", value_name = "STRATEGY")]
+        strategy: Strategy,
+        /// Always open with this word
+        #[clap(long, value_name = "WORD")]
+        start: Option<String>,
+        /// Output format
+        #[clap(
+            long = "tree-format",
+            arg_enum,
+            default_value = "json",
+            value_name = "FORMAT"
+        )]
+        tree_format: TreeFormat,
+        /// Write the tree to this file instead of stdout
+        #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// List the dictionary words matching a pattern like `c?a?e -rt +s`
+    ///
+    /// `?` is any letter, `-` lists letters the word must not contain and `+` letters it must contain.
+    Query {
+        #[clap(
+            value_name = "PATTERN",
+            required = true,
+            multiple_values = true,
+            allow_hyphen_values = true
+        )]
+        pattern: Vec<String>,
+    },
+    /// Compare how well words split the dictionary: reply patterns, worst case, expected remaining and entropy
+    Eval {
+        #[clap(value_name = "WORD", required = true, multiple_values = true)]
+        words: Vec<String>,
+    },
+    /// Maintain dictionary files
+    Dict {
+        #[clap(subcommand)]
+        command: DictCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum DictCommand {
+    /// Report duplicates, malformed words, sloppy formatting and lines that would be dropped, exits non-zero on problems
+    Check {
+        #[clap(parse(from_os_str), value_name = "FILE")]
+        file: PathBuf,
+    },
+    /// Report letter frequencies overall and per position, repeated letters, common bigrams and the rarest words
+    Stats {
+        /// Dictionary to analyze, defaults to the dictionary in use
+        #[clap(parse(from_os_str), value_name = "FILE")]
+        file: Option<PathBuf>,
+        /// How many bigrams and rare words to list
+        #[clap(short, long, default_value_t = 10, value_name = "COUNT")]
+        top: usize,
+    },
 }
 
 #[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, ArgEnum)]
@@ -62,18 +271,387 @@ fn setup_logger(log_level: Option<MyLogLevel>) -> Result<(), FailedToStartLogger
         .map_err(|_| FailedToStartLogger)
 }
 
+/// Load the answers to simulate from a file, falling back to the dictionary
+fn load_answers(answers: Option<PathBuf>, word_list: &[String]) -> Option<Vec<String>> {
+    match answers {
+        Some(path) => match load_word_list(&path) {
+            Ok(answers) => Some(answers),
+            Err(error) => {
+                eprintln!("unable to read answers {}: {}", path.display(), error);
+                None
+            }
+        },
+        None => Some(word_list.to_vec()),
+    }
+}
+
+/// Prefix of dictionary paths that name an embedded list instead of a file
+const EMBEDDED_PREFIX: &str = "embedded:";
+
+/// Merge the dictionaries (or the embedded one) and includes in order, then remove the excludes
+///
+/// Skipped lines are reported for every file, `None` if a file could not be read.
+fn load_dictionaries(
+    dictionaries: &[PathBuf],
+    includes: &[PathBuf],
+    excludes: &[PathBuf],
+) -> Option<(Dictionary, Vec<SourceSummary>)> {
+    let load = |path: &PathBuf| {
+        if let Some(name) = path
+            .to_str()
+            .and_then(|path| path.strip_prefix(EMBEDDED_PREFIX))
+        {
+            let list = embedded_word_list(name);
+            if list.is_none() {
+                let names: Vec<_> = embedded_word_lists().map(|list| list.name()).collect();
+                eprintln!(
+                    "no embedded list {:?}, this build has: {}",
+                    name,
+                    names.join(", ")
+                );
+            }
+            return list.map(|list| Dictionary::from_packed(list, list.name() != "guesses"));
+        }
+
+        match Dictionary::load(path) {
+            Ok(dictionary) => {
+                for skipped in &dictionary.skipped {
+                    eprintln!("{}: skipped {}", path.display(), skipped);
+                }
+                Some(dictionary)
+            }
+            Err(error) => {
+                eprintln!("unable to read dictionary {}: {}", path.display(), error);
+                None
+            }
+        }
+    };
+
+    let mut combined = Dictionary::default();
+    let mut sources = Vec::new();
+    let mut merge = |name: String, kind: SourceKind, dictionary: Dictionary| {
+        let words = dictionary.entries.len();
+        let changed = combined.merge(dictionary);
+        sources.push(SourceSummary {
+            name,
+            kind,
+            words,
+            changed,
+        });
+    };
+    if dictionaries.is_empty() {
+        if DEFAULT_WORD_LIST.is_empty() {
+            eprintln!("no dictionary is embedded in this build, pass one with --dictionary");
+            return None;
+        }
+        merge(
+            "embedded".to_string(),
+            SourceKind::Dictionary,
+            Dictionary::embedded(),
+        );
+    }
+    for path in dictionaries {
+        merge(
+            path.display().to_string(),
+            SourceKind::Dictionary,
+            load(path)?,
+        );
+    }
+    for path in includes {
+        merge(path.display().to_string(), SourceKind::Include, load(path)?);
+    }
+    for path in excludes {
+        let excluded = load(path)?;
+        sources.push(SourceSummary {
+            name: path.display().to_string(),
+            kind: SourceKind::Exclude,
+            words: excluded.entries.len(),
+            changed: combined.remove(&excluded),
+        });
+    }
+
+    Some((combined, sources))
+}
+
 fn main() {
     let Args {
         log_level,
-        dictionary,
+        dictionaries,
+        includes,
+        excludes,
         hide_letter_frequency,
+        format,
+        past_answers,
+        before,
+        strategy,
+        rpc,
+        command,
     } = Args::parse();
+    if rpc && command.is_some() {
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--rpc cannot be used with a subcommand",
+            )
+            .exit();
+    }
 
     if setup_logger(log_level).is_err() {
         eprintln!("failed to start logger");
         return;
     }
 
-    let _ = Engine::new(dictionary, !hide_letter_frequency).start();
+    let (dictionary, sources) = match load_dictionaries(&dictionaries, &includes, &excludes) {
+        Some(dictionary) => dictionary,
+        None => return,
+    };
+    let (word_list, guesses, priors) = (
+        dictionary.answers(),
+        dictionary.guesses(),
+        dictionary.priors(),
+    );
+    // Only worth reporting when the embedded dictionary was not used as is
+    let sources = if dictionaries.is_empty() && includes.is_empty() && excludes.is_empty() {
+        Vec::new()
+    } else {
+        sources
+    };
+    if let Some(before) = before.as_deref().filter(|before| !is_iso_date(before)) {
+        eprintln!("illegal date {:?}: expected YYYY-MM-DD", before);
+        return;
+    }
+    let excluded = match past_answers.map(|path| (load_past_answers(&path), path)) {
+        Some((Ok(past_answers), _)) => used_answers(&past_answers, before.as_deref()),
+        Some((Err(error), path)) => {
+            eprintln!("unable to read past answers {}: {}", path.display(), error);
+            return;
+        }
+        None => Default::default(),
+    };
+    // The interactive engine explains why past answers are left out, every other command never sees them
+    let (word_list, excluded) = if command.is_none() && !rpc {
+        (word_list, excluded)
+    } else {
+        let word_list = word_list
+            .into_iter()
+            .filter(|word| !excluded.contains(word))
+            .collect();
+        (word_list, HashSet::new())
+    };
+
+    let priors = priors.map(Arc::new);
+
+    match command {
+        Some(Command::Serve { port, host }) => {
+            let address = format!("{}:{}", host, port);
+            let mut server = HttpServer::new(word_list);
+            if let Some(priors) = priors {
+                server = server.with_priors(priors);
+            }
+            if let Err(error) = server.serve(&address) {
+                eprintln!("failed to serve on {}: {}", address, error);
+                return;
+            }
+        }
+        Some(Command::Simulate {
+            answers,
+            sample,
+            worst,
+            strategy,
+            start,
+        }) => {
+            let answers = match load_answers(answers, &word_list) {
+                Some(answers) => answers,
+                None => return,
+            };
+            let start = match start.map(|start| parse_guess(&start)).transpose() {
+                Ok(start) => start,
+                Err(error) => {
+                    eprintln!("illegal starting word: {}", error);
+                    return;
+                }
+            };
+            run_simulation(
+                &word_list,
+                &answers,
+                sample,
+                worst,
+                strategy,
+                start.as_deref(),
+                priors,
+            );
+        }
+        Some(Command::Tournament {
+            answers,
+            sample,
+            mut strategies,
+            starts,
+            list,
+            csv,
+        }) => {
+            let answers = match load_answers(answers, &word_list) {
+                Some(answers) => answers,
+                None => return,
+            };
+            let starts = match starts
+                .iter()
+                .map(|start| parse_guess(start).map(Some))
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(starts) if starts.is_empty() => vec![None],
+                Ok(starts) => starts,
+                Err(error) => {
+                    eprintln!("illegal starting word: {}", error);
+                    return;
+                }
+            };
+            if strategies.is_empty() {
+                strategies = Strategy::ALL.to_vec();
+            }
+            let contestants = strategies
+                .iter()
+                .flat_map(|&strategy| {
+                    starts.iter().map(move |opener| Contestant {
+                        strategy,
+                        opener: opener.clone(),
+                    })
+                })
+                .collect();
+            if let Err(error) = run_tournament(
+                &word_list,
+                &answers,
+                sample,
+                contestants,
+                list,
+                csv.as_deref(),
+                priors,
+            ) {
+                eprintln!("failed to write tournament results: {}", error);
+            }
+        }
+        Some(Command::Openers {
+            answers,
+            sort,
+            top,
+            csv,
+        }) => {
+            let answers = match load_answers(answers, &word_list) {
+                Some(answers) => answers,
+                None => return,
+            };
+            if let Err(error) = run_openers(
+                &guesses,
+                &answers,
+                sort,
+                top,
+                csv.as_deref(),
+                priors.as_deref(),
+            ) {
+                eprintln!("failed to write openers: {}", error);
+            }
+        }
+        Some(Command::Opening {
+            answers,
+            length,
+            pool,
+            top,
+        }) => {
+            if !(1..=MAX_OPENING_LEN).contains(&length) {
+                eprintln!(
+                    "illegal opening length: expected 1 to {} words",
+                    MAX_OPENING_LEN
+                );
+                return;
+            }
+            let answers = match load_answers(answers, &word_list) {
+                Some(answers) => answers,
+                None => return,
+            };
+            run_opening_search(&guesses, &answers, length, pool, top);
+        }
+        Some(Command::Tree {
+            strategy,
+            start,
+            tree_format,
+            output,
+        }) => {
+            let start = match start.map(|start| parse_guess(&start)).transpose() {
+                Ok(start) => start,
+                Err(error) => {
+                    eprintln!("illegal starting word: {}", error);
+                    return;
+                }
+            };
+            if let Err(error) = run_tree(
+                &word_list,
+                strategy,
+                start.as_deref(),
+                tree_format,
+                output.as_deref(),
+            ) {
+                eprintln!("failed to write decision tree: {}", error);
+            }
+        }
+        Some(Command::Query { pattern }) => match pattern.join(" ").parse::<Pattern>() {
+            Ok(pattern) => {
+                let matches = pattern.matches(&guesses);
+                for word in &matches {
+                    println!("{}", word);
+                }
+                eprintln!("{} matches", matches.len());
+            }
+            Err(error) => eprintln!("illegal pattern: {}", error),
+        },
+        Some(Command::Eval { words }) => {
+            match words
+                .iter()
+                .map(|word| parse_guess(word))
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(words) => {
+                    print_evaluations(&evaluate_guesses(&words, &word_list, priors.as_deref()))
+                }
+                Err(error) => eprintln!("illegal word: {}", error),
+            }
+        }
+        Some(Command::Dict {
+            command: DictCommand::Check { file },
+        }) => match run_check(&file) {
+            Ok(true) => {}
+            Ok(false) => std::process::exit(1),
+            Err(error) => {
+                eprintln!("unable to read dictionary {}: {}", file.display(), error);
+                std::process::exit(1);
+            }
+        },
+        Some(Command::Dict {
+            command: DictCommand::Stats { file, top },
+        }) => {
+            let word_list = match file {
+                Some(path) => match load_word_list(&path) {
+                    Ok(word_list) => word_list,
+                    Err(error) => {
+                        eprintln!("unable to read dictionary {}: {}", path.display(), error);
+                        return;
+                    }
+                },
+                None => word_list,
+            };
+            run_stats(&word_list, top);
+        }
+        None if rpc => {
+            let mut server = RpcServer::new(word_list);
+            if let Some(priors) = priors {
+                server = server.with_priors(priors);
+            }
+            server.run();
+        }
+        None => {
+            let _ = Engine::new(word_list, !hide_letter_frequency, format, strategy, priors)
+                .with_excluded_answers(excluded)
+                .with_sources(sources)
+                .start();
+        }
+    }
     debug!("successfully exited");
 }
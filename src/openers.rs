@@ -0,0 +1,160 @@
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use clap::ArgEnum;
+use wordle_helper::dictionary::Priors;
+use wordle_helper::evaluation::{evaluate_guesses, GuessEvaluation};
+use wordle_helper::game::valid_words;
+use wordle_helper::opening::best_openings;
+
+/// How to order the opener table, best first
+#[derive(Eq, PartialEq, Debug, Copy, Clone, ArgEnum)]
+pub enum OpenerOrder {
+    /// Most expected information
+    Entropy,
+    /// Fewest expected remaining answers
+    Expected,
+    /// Smallest worst-case bucket
+    Worst,
+    /// Most distinct reply patterns
+    Patterns,
+}
+
+impl OpenerOrder {
+    fn compare(self, a: &GuessEvaluation, b: &GuessEvaluation) -> Ordering {
+        match self {
+            OpenerOrder::Entropy => b.entropy.partial_cmp(&a.entropy),
+            OpenerOrder::Expected => a.expected_remaining.partial_cmp(&b.expected_remaining),
+            OpenerOrder::Worst => Some(a.largest_bucket.cmp(&b.largest_bucket)),
+            OpenerOrder::Patterns => Some(b.buckets.cmp(&a.buckets)),
+        }
+        .unwrap_or(Ordering::Equal)
+        .then_with(|| a.guess.cmp(&b.guess))
+    }
+}
+
+/// Score every allowed guess as a first move against the answers (weighed by `priors` if given), print the best and optionally export all of them
+pub fn run_openers(
+    guesses: &[String],
+    answers: &[String],
+    order: OpenerOrder,
+    top_len: usize,
+    csv_path: Option<&Path>,
+    priors: Option<&Priors>,
+) -> std::io::Result<()> {
+    let (guesses, answers) = (valid_words(guesses), valid_words(answers));
+    let mut evaluations = evaluate_guesses(&guesses, &answers, priors);
+    evaluations.sort_by(|a, b| order.compare(a, b));
+
+    println!(
+        "Scored {} openers against {} answers",
+        evaluations.len(),
+        answers.len()
+    );
+    println!(
+        "{:>5}  {:5}  {:>7}  {:>8}  {:>5}  {:>8}",
+        "rank", "word", "entropy", "expected", "worst", "patterns"
+    );
+    for (index, evaluation) in evaluations.iter().take(top_len).enumerate() {
+        println!(
+            "{:>5}  {:5}  {:>7.3}  {:>8.2}  {:>5}  {:>8}",
+            index + 1,
+            evaluation.guess,
+            evaluation.entropy,
+            evaluation.expected_remaining,
+            evaluation.largest_bucket,
+            evaluation.buckets
+        );
+    }
+
+    if let Some(csv_path) = csv_path {
+        let mut writer = BufWriter::new(File::create(csv_path)?);
+        writeln!(writer, "rank,word,entropy,expected,worst,patterns,answer")?;
+        for (index, evaluation) in evaluations.iter().enumerate() {
+            writeln!(
+                writer,
+                "{},{},{:.6},{:.6},{},{},{}",
+                index + 1,
+                evaluation.guess,
+                evaluation.entropy,
+                evaluation.expected_remaining,
+                evaluation.largest_bucket,
+                evaluation.buckets,
+                evaluation.possible_answer
+            )?;
+        }
+        writer.flush()?;
+        println!(
+            "Wrote {} openers to {}",
+            evaluations.len(),
+            csv_path.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Print a table of guesses as evaluated, with whether each can be the answer
+pub fn print_evaluations(evaluations: &[GuessEvaluation]) {
+    println!(
+        "{:5}  {:>7}  {:>8}  {:>5}  {:>8}  {:>6}",
+        "word", "entropy", "expected", "worst", "patterns", "answer"
+    );
+    for evaluation in evaluations {
+        println!(
+            "{:5}  {:>7.3}  {:>8.2}  {:>5}  {:>8}  {:>6}",
+            evaluation.guess,
+            evaluation.entropy,
+            evaluation.expected_remaining,
+            evaluation.largest_bucket,
+            evaluation.buckets,
+            if evaluation.possible_answer {
+                "yes"
+            } else {
+                "no"
+            }
+        );
+    }
+}
+
+/// Search for the fixed openings of `length` words that split the answers the most and print them
+pub fn run_opening_search(
+    guesses: &[String],
+    answers: &[String],
+    length: usize,
+    pool: usize,
+    top_len: usize,
+) {
+    let openings = best_openings(guesses, answers, length, pool, top_len);
+
+    println!(
+        "Best fixed openings of {} words from the top {} openers against {} answers",
+        length,
+        pool.min(guesses.len()),
+        answers.len()
+    );
+    println!(
+        "{:>5}  {:width$}  {:>8}  {:>5}  {:>8}  {:>7}",
+        "rank",
+        "words",
+        "expected",
+        "worst",
+        "patterns",
+        "entropy",
+        width = length * 6 - 1
+    );
+    for (index, opening) in openings.iter().enumerate() {
+        println!(
+            "{:>5}  {:width$}  {:>8.3}  {:>5}  {:>8}  {:>7.3}",
+            index + 1,
+            opening.words.join(" "),
+            opening.expected_remaining,
+            opening.largest_bucket,
+            opening.buckets,
+            opening.entropy,
+            width = length * 6 - 1
+        );
+    }
+}
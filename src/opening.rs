@@ -0,0 +1,190 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use log::debug;
+use serde::Serialize;
+
+use crate::evaluation::evaluate_guesses;
+use crate::game::{response_for, response_index, valid_words, NUM_RESPONSES};
+
+/// Longest fixed opening that can be searched, keeps joint response codes within a `u64`
+pub const MAX_OPENING_LEN: usize = 4;
+
+/// A fixed set of opening guesses played regardless of their replies
+#[derive(Debug, Clone, Serialize)]
+pub struct OpeningSequence {
+    pub words: Vec<String>,
+    /// Number of distinct combined reply patterns
+    pub buckets: usize,
+    /// Most answers sharing the same combined replies (worst case remaining)
+    pub largest_bucket: usize,
+    /// Average number of answers remaining after playing every word
+    pub expected_remaining: f64,
+    /// Expected information gained by the whole sequence, in bits
+    pub entropy: f64,
+}
+
+impl OpeningSequence {
+    /// Partition the answers by the combined replies of several guesses, given each guess's response codes per answer
+    fn new(words: Vec<String>, codes: &[&[u8]]) -> Self {
+        let answers = codes.first().map_or(0, |codes| codes.len());
+        let mut keys: Vec<u64> = (0..answers)
+            .map(|answer| {
+                codes.iter().fold(0, |key, codes| {
+                    key * NUM_RESPONSES as u64 + codes[answer] as u64
+                })
+            })
+            .collect();
+        keys.sort_unstable();
+
+        let total = answers as f64;
+        let mut sequence = Self {
+            words,
+            buckets: 0,
+            largest_bucket: 0,
+            expected_remaining: 0.0,
+            entropy: 0.0,
+        };
+        for bucket in keys.chunk_by(|a, b| a == b) {
+            let size = bucket.len();
+            let probability = size as f64 / total;
+            sequence.buckets += 1;
+            sequence.largest_bucket = sequence.largest_bucket.max(size);
+            sequence.expected_remaining += probability * size as f64;
+            sequence.entropy -= probability * probability.log2();
+        }
+
+        sequence
+    }
+
+    fn compare(&self, other: &Self) -> Ordering {
+        self.expected_remaining
+            .partial_cmp(&other.expected_remaining)
+            .unwrap_or(Ordering::Equal)
+            .then(other.buckets.cmp(&self.buckets))
+            .then_with(|| self.words.cmp(&other.words))
+    }
+}
+
+/// Find the fixed openings of `length` words that leave the fewest answers expected, best first
+///
+/// Only the `pool` best single openers (by entropy) are combined, and each round only extends the `pool` best shorter openings.
+pub fn best_openings(
+    guesses: &[String],
+    answers: &[String],
+    length: usize,
+    pool: usize,
+    count: usize,
+) -> Vec<OpeningSequence> {
+    assert!(
+        (1..=MAX_OPENING_LEN).contains(&length),
+        "opening length must be between 1 and {}",
+        MAX_OPENING_LEN
+    );
+    // Both lists go straight into response_for, which only takes lowercase words of the right length
+    let (guesses, answers) = (valid_words(guesses), valid_words(answers));
+    let mut singles = evaluate_guesses(&guesses, &answers, None);
+    singles.sort_by(|a, b| {
+        b.entropy
+            .partial_cmp(&a.entropy)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.guess.cmp(&b.guess))
+    });
+    singles.truncate(pool);
+    debug!(
+        "combining {} openers into sequences of {}",
+        singles.len(),
+        length
+    );
+
+    let pool: Vec<_> = singles.into_iter().map(|single| single.guess).collect();
+    let codes: Vec<Vec<u8>> = pool
+        .iter()
+        .map(|guess| {
+            answers
+                .iter()
+                .map(|answer| response_index(&response_for(guess, answer)) as u8)
+                .collect()
+        })
+        .collect();
+
+    let score = |sequences: Vec<Vec<usize>>| {
+        let mut scored: Vec<_> = sequences
+            .into_iter()
+            .map(|sequence| {
+                let words = sequence.iter().map(|&index| pool[index].clone()).collect();
+                let sequence_codes: Vec<_> = sequence
+                    .iter()
+                    .map(|&index| codes[index].as_slice())
+                    .collect();
+                let opening = OpeningSequence::new(words, &sequence_codes);
+                (sequence, opening)
+            })
+            .collect();
+        scored.sort_by(|(_, a), (_, b)| a.compare(b));
+
+        scored
+    };
+
+    // Sequences are kept as sorted indices into the pool so each set of words is only scored once
+    let mut scored = score((0..pool.len()).map(|index| vec![index]).collect());
+    for _ in 1..length {
+        let mut seen = HashSet::new();
+        let extended = scored
+            .iter()
+            .take(pool.len())
+            .flat_map(|(sequence, _)| {
+                (0..pool.len())
+                    .filter(|index| !sequence.contains(index))
+                    .map(move |index| {
+                        let mut extended = sequence.clone();
+                        extended.push(index);
+                        extended.sort_unstable();
+                        extended
+                    })
+            })
+            .filter(|sequence| seen.insert(sequence.clone()))
+            .collect();
+        scored = score(extended);
+    }
+
+    scored
+        .into_iter()
+        .take(count)
+        .map(|(_, opening)| opening)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn malformed_words_are_left_out() {
+        let guesses = words(&["CRANE", "", "sl8te", "moist\r"]);
+        let answers = words(&["crane", "moist", "", "toolong"]);
+        let openings = best_openings(&guesses, &answers, 1, 10, 10);
+
+        let mut openers: Vec<_> = openings
+            .iter()
+            .map(|opening| opening.words[0].as_str())
+            .collect();
+        openers.sort_unstable();
+        assert_eq!(openers, ["crane", "moist"]);
+    }
+
+    #[test]
+    fn pairs_that_split_every_answer_come_first() {
+        let guesses = words(&["crane", "moist", "fuzzy"]);
+        let answers = words(&["crane", "moist", "crime", "toils"]);
+        let best = &best_openings(&guesses, &answers, 2, 3, 1)[0];
+
+        assert_eq!(best.buckets, answers.len());
+        assert_eq!(best.largest_bucket, 1);
+        assert!((best.entropy - 2.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,109 @@
+//! First and second guesses of [Strategy::Entropy] for the embedded dictionary, precomputed by `build.rs`.
+
+use crate::game::{format_response, Response, Turn};
+use crate::strategy::Strategy;
+use crate::word_picker::HardModeWordPicker;
+
+include!(concat!(env!("OUT_DIR"), "/opening_book.rs"));
+
+/// FNV-1a over every word followed by a newline, identifies the dictionary the book was generated from
+pub fn dictionary_hash<S: AsRef<str>>(word_list: &[S]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for word in word_list {
+        for &byte in word.as_ref().as_bytes().iter().chain(b"\n") {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    hash
+}
+
+/// If the book was generated from this dictionary
+pub fn applies_to<S: AsRef<str>>(word_list: &[S]) -> bool {
+    dictionary_hash(word_list) == OPENING_BOOK_DICTIONARY_HASH
+}
+
+/// The book's next guess after `turns`, `None` past the second turn or once the game left the book
+///
+/// Only valid for the dictionary the book was generated from, see [applies_to].
+pub fn book_guess(turns: &[Turn]) -> Option<&'static str> {
+    match turns {
+        [] => Some(OPENING_BOOK_FIRST_GUESS),
+        [first] => {
+            let guess: String = first.iter().map(|cell| cell.letter).collect();
+            if guess != OPENING_BOOK_FIRST_GUESS {
+                return None;
+            }
+            let replies: Response = first.iter().map(|cell| cell.reply).collect();
+            let reply = format_response(&replies);
+            OPENING_BOOK_SECOND_GUESSES
+                .binary_search_by(|&(book_reply, _)| book_reply.cmp(reply.as_str()))
+                .ok()
+                .map(|index| OPENING_BOOK_SECOND_GUESSES[index].1)
+        }
+        _ => None,
+    }
+}
+
+/// The strategy's next guess, looked up in the book when `in_book` and the strategy is [Strategy::Entropy]
+pub fn next_guess(
+    strategy: Strategy,
+    in_book: bool,
+    word_picker: &HardModeWordPicker,
+    turns: &[Turn],
+) -> Option<String> {
+    let book = match (strategy, in_book) {
+        (Strategy::Entropy, true) => book_guess(turns),
+        _ => None,
+    };
+
+    book.map(String::from)
+        .or_else(|| strategy.pick(word_picker).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{make_turn, parse_response};
+
+    #[cfg(feature = "embed-answers")]
+    #[test]
+    fn book_agrees_with_entropy_strategy() {
+        let word_list = crate::default_word_list();
+        assert!(applies_to(&word_list));
+        let word_picker = HardModeWordPicker::new(&word_list);
+        assert_eq!(
+            Strategy::Entropy.pick(&word_picker).map(String::as_str),
+            Some(OPENING_BOOK_FIRST_GUESS)
+        );
+
+        for &(reply, second_guess) in &OPENING_BOOK_SECOND_GUESSES {
+            let response = parse_response(reply).expect("book replies are reply symbols");
+            let mut word_picker = word_picker.clone();
+            word_picker.take_turn(make_turn(OPENING_BOOK_FIRST_GUESS, &response));
+            assert_eq!(
+                Strategy::Entropy.pick(&word_picker).map(String::as_str),
+                Some(second_guess),
+                "second guess after {}",
+                reply
+            );
+        }
+    }
+
+    #[test]
+    fn book_is_left_after_other_openers_and_the_second_turn() {
+        let response = parse_response("..-..").unwrap();
+        let off_book = make_turn("crane", &response);
+        assert_eq!(book_guess(&[off_book]), None);
+
+        let first = make_turn(OPENING_BOOK_FIRST_GUESS, &response);
+        assert!(book_guess(std::slice::from_ref(&first)).is_some());
+        assert_eq!(book_guess(&[first.clone(), first]), None);
+    }
+
+    #[test]
+    fn other_dictionaries_do_not_use_the_book() {
+        assert!(!applies_to(&["crane", "slate"]));
+    }
+}
@@ -1,46 +1,105 @@
 use std::io::{stdin_locked, stdout, BufRead, Lines, StdinLock, Write};
 use std::iter::Flatten;
 
-use crate::engine::Exit;
-use crate::game::{GameCell, Guess, Reply};
-use crate::game::{Response, Turn};
-use crate::NUM_LETTERS;
+use wordle_helper::constraints::Constraints;
+use wordle_helper::game::{Guess, Reply, Response, REPLY_MISS, REPLY_PARTIAL, REPLY_SUCCESS};
+use wordle_helper::query::Pattern;
+use wordle_helper::solver::parse_guess;
+use wordle_helper::NUM_LETTERS;
 
-const REPLY_SUCCESS: char = '+';
-const REPLY_MISS: char = '.';
-const REPLY_PARTIAL: char = '-';
+use crate::engine::Exit;
 
 pub enum ReadTurnFlags {
     Exit(Exit),
-    Win,
+    /// The game was won, with the winning guess if it was entered before the winning reply
+    Win(Option<Guess>),
+    /// Search the remaining words with a pattern instead of taking a turn
+    Query(Pattern),
+    /// Narrow the remaining words with constraints read off a board instead of taking a turn
+    Known(Constraints),
+    /// Compare how well some words split the remaining words instead of taking a turn
+    Eval(Vec<String>),
+    /// Explain why a word is no longer a potential solution instead of taking a turn
+    Why(String),
 }
 
+/// Prefix of a pattern query entered instead of a guess or reply (ie. `query c?a?e -rt +s`)
+const QUERY_COMMAND: &str = "query ";
+/// Prefix of constraints entered instead of a guess or reply (ie. `known green a in 3, yellow s not in 1, gray r t l`)
+const KNOWN_COMMAND: &str = "known ";
+/// Prefix of words to evaluate entered instead of a guess or reply (ie. `eval crane slate`)
+const EVAL_COMMAND: &str = "eval ";
+/// Prefix of a word to explain entered instead of a guess or reply (ie. `why crane`)
+const WHY_COMMAND: &str = "why ";
+
 pub struct Parser {
     lines: Flatten<Lines<StdinLock<'static>>>,
     winning_reply: String,
+    prompt_on_stderr: bool,
 }
 
 impl Parser {
-    pub fn new() -> Self {
+    /// Create a parser reading from stdin, prompts can be sent to stderr to keep stdout machine-readable
+    pub fn new(prompt_on_stderr: bool) -> Self {
         Self {
             lines: stdin_locked().lines().flatten(),
             winning_reply: String::from(REPLY_SUCCESS).repeat(NUM_LETTERS),
+            prompt_on_stderr,
         }
     }
 }
 
 impl Parser {
+    fn print_prompt(&self, prompt: &str) {
+        if self.prompt_on_stderr {
+            eprint!("{}: ", prompt);
+        } else {
+            print!("{}: ", prompt);
+            stdout()
+                .lock()
+                .flush()
+                .expect("failed to flush prompt stdout (likely too long)");
+        }
+    }
+
+    /// Ask a yes or no question, anything but yes is taken as no
+    pub fn read_confirmation(&mut self, prompt: &str) -> Result<bool, ReadTurnFlags> {
+        self.print_prompt(&format!("{} [y/N]", prompt));
+        let input = self.lines.next().unwrap_or_default();
+
+        match input.trim().to_ascii_lowercase().as_str() {
+            "exit" | "quit" | "q" => Err(ReadTurnFlags::Exit(Exit)),
+            "y" | "yes" => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    /// Ask to pick one of `count` numbered options, returns its index or `None` for anything else
+    pub fn read_choice(
+        &mut self,
+        prompt: &str,
+        count: usize,
+    ) -> Result<Option<usize>, ReadTurnFlags> {
+        self.print_prompt(&format!("{} [1-{}]", prompt, count));
+        let input = self.lines.next().unwrap_or_default();
+
+        match input.trim().to_ascii_lowercase().as_str() {
+            "exit" | "quit" | "q" => Err(ReadTurnFlags::Exit(Exit)),
+            choice => Ok(choice
+                .parse::<usize>()
+                .ok()
+                .filter(|choice| (1..=count).contains(choice))
+                .map(|choice| choice - 1)),
+        }
+    }
+
     fn read_input(
         &mut self,
         input_name: &'static str,
         prompt: &str,
     ) -> Result<Guess, ReadTurnFlags> {
         loop {
-            print!("{}: ", prompt);
-            stdout()
-                .lock()
-                .flush()
-                .expect("failed to flush prompt stdout (likely too long)");
+            self.print_prompt(prompt);
             let input = if let Some(input) = self.lines.next() {
                 input
             } else {
@@ -52,7 +111,35 @@ impl Parser {
 
             match guess.as_str() {
                 "exit" | "quit" | "q" => break Err(ReadTurnFlags::Exit(Exit)),
-                _ if guess == self.winning_reply => break Err(ReadTurnFlags::Win),
+                _ if guess == self.winning_reply => break Err(ReadTurnFlags::Win(None)),
+                _ if guess.starts_with(QUERY_COMMAND) => {
+                    match guess[QUERY_COMMAND.len()..].parse() {
+                        Ok(pattern) => break Err(ReadTurnFlags::Query(pattern)),
+                        Err(error) => eprintln!("illegal query: {}", error),
+                    }
+                }
+                _ if guess.starts_with(KNOWN_COMMAND) => {
+                    match guess[KNOWN_COMMAND.len()..].parse() {
+                        Ok(constraints) => break Err(ReadTurnFlags::Known(constraints)),
+                        Err(error) => eprintln!("illegal constraints: {}", error),
+                    }
+                }
+                _ if guess.starts_with(EVAL_COMMAND) => {
+                    match guess[EVAL_COMMAND.len()..]
+                        .split_whitespace()
+                        .map(parse_guess)
+                        .collect()
+                    {
+                        Ok(words) => break Err(ReadTurnFlags::Eval(words)),
+                        Err(error) => eprintln!("illegal evaluation: {}", error),
+                    }
+                }
+                _ if guess.starts_with(WHY_COMMAND) => {
+                    match parse_guess(&guess[WHY_COMMAND.len()..]) {
+                        Ok(word) => break Err(ReadTurnFlags::Why(word)),
+                        Err(error) => eprintln!("illegal word: {}", error),
+                    }
+                }
                 _ => match Guess::from(&guess) {
                     // break Ok(Guess::from(&guess))
                     Ok(guess) => {
@@ -71,7 +158,7 @@ impl Parser {
         }
     }
 
-    fn read_guess(&mut self) -> Result<Guess, ReadTurnFlags> {
+    pub fn read_guess(&mut self) -> Result<Guess, ReadTurnFlags> {
         const PROMPT: &str = "input guess";
         loop {
             let input = self.read_input("input", PROMPT)?;
@@ -85,7 +172,7 @@ impl Parser {
         }
     }
 
-    fn read_reply(&mut self) -> Result<Response, ReadTurnFlags> {
+    pub fn read_reply(&mut self) -> Result<Response, ReadTurnFlags> {
         let prompt = format!(
             "input reply (miss: '{}', hit: '{}' partial: '{}')",
             REPLY_MISS, REPLY_SUCCESS, REPLY_PARTIAL
@@ -100,11 +187,9 @@ impl Parser {
 
             let mut response = Response::new();
             for letter in input.chars() {
-                let reply = match letter {
-                    REPLY_SUCCESS => Reply::Success,
-                    REPLY_MISS => Reply::Miss,
-                    REPLY_PARTIAL => Reply::Partial,
-                    _ => {
+                let reply = match Reply::from_symbol(letter) {
+                    Some(reply) => reply,
+                    None => {
                         eprintln!("illegal input: expected alphabetical characters");
                         continue 'outer;
                     }
@@ -115,16 +200,4 @@ impl Parser {
             break Ok(response);
         }
     }
-
-    pub fn read_turn(&mut self) -> Result<Turn, ReadTurnFlags> {
-        let guess = self.read_guess()?;
-        let response = self.read_reply()?;
-        let mut turn: Turn = Default::default();
-
-        for (letter, &reply) in guess.chars().zip(response.iter()) {
-            turn.push(GameCell { letter, reply });
-        }
-
-        Ok(turn)
-    }
 }
@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use log::{debug, info};
+
+/// An answer the daily game already used, with the day it was used if known
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PastAnswer {
+    /// `YYYY-MM-DD`
+    pub date: Option<String>,
+    pub word: String,
+}
+
+/// If a date is formatted as `YYYY-MM-DD`, these compare correctly as strings
+pub fn is_iso_date(date: &str) -> bool {
+    let bytes = date.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return false;
+    }
+    let number = |range: std::ops::Range<usize>| {
+        Some(&date[range])
+            .filter(|digits| digits.bytes().all(|byte| byte.is_ascii_digit()))
+            .and_then(|digits| digits.parse::<u32>().ok())
+    };
+
+    matches!(
+        (number(0..4), number(5..7), number(8..10)),
+        (Some(_), Some(1..=12), Some(1..=31))
+    )
+}
+
+/// Load past answers from a text file, one per line as `word` or `YYYY-MM-DD word` (whitespace or a comma in between)
+pub fn load_past_answers<P: AsRef<Path>>(path: P) -> io::Result<Vec<PastAnswer>> {
+    let path = path.as_ref();
+    debug!("loading past answers {}", path.display());
+    let reader = BufReader::new(File::open(path)?);
+
+    let mut past_answers = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let mut fields = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|field| !field.is_empty());
+        let past_answer = match (fields.next(), fields.next(), fields.next()) {
+            (None, _, _) => continue,
+            (Some(word), None, _) => PastAnswer {
+                date: None,
+                word: word.to_ascii_lowercase(),
+            },
+            (Some(date), Some(word), None) if is_iso_date(date) => PastAnswer {
+                date: Some(date.to_string()),
+                word: word.to_ascii_lowercase(),
+            },
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "line {}: expected `word` or `YYYY-MM-DD word`, got {:?}",
+                        index + 1,
+                        line
+                    ),
+                ))
+            }
+        };
+        past_answers.push(past_answer);
+    }
+    info!("processed {} past answers", past_answers.len());
+
+    Ok(past_answers)
+}
+
+/// Answers already used on the day `before` (`YYYY-MM-DD`), or by now if `None`
+///
+/// Undated answers are always considered used.
+pub fn used_answers(past_answers: &[PastAnswer], before: Option<&str>) -> HashSet<String> {
+    past_answers
+        .iter()
+        .filter(|past_answer| match (&past_answer.date, before) {
+            (Some(date), Some(before)) => date.as_str() < before,
+            _ => true,
+        })
+        .map(|past_answer| past_answer.word.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn past_answer(date: Option<&str>, word: &str) -> PastAnswer {
+        PastAnswer {
+            date: date.map(String::from),
+            word: word.to_string(),
+        }
+    }
+
+    #[test]
+    fn every_answer_is_used_by_now() {
+        let past_answers = [
+            past_answer(Some("2022-01-01"), "cigar"),
+            past_answer(None, "rebut"),
+        ];
+
+        assert_eq!(
+            used_answers(&past_answers, None),
+            HashSet::from([String::from("cigar"), String::from("rebut")])
+        );
+    }
+
+    #[test]
+    fn answers_from_the_day_on_are_not_used_yet() {
+        let past_answers = [
+            past_answer(Some("2021-12-31"), "cigar"),
+            past_answer(Some("2022-01-01"), "rebut"),
+            past_answer(Some("2022-01-02"), "sissy"),
+        ];
+
+        assert_eq!(
+            used_answers(&past_answers, Some("2022-01-01")),
+            HashSet::from([String::from("cigar")])
+        );
+    }
+
+    #[test]
+    fn undated_answers_are_always_used() {
+        let past_answers = [past_answer(None, "rebut")];
+
+        assert_eq!(
+            used_answers(&past_answers, Some("2000-01-01")),
+            HashSet::from([String::from("rebut")])
+        );
+    }
+
+    #[test]
+    fn iso_dates_are_recognized() {
+        assert!(is_iso_date("2022-01-31"));
+        assert!(!is_iso_date("2022-13-01"));
+        assert!(!is_iso_date("2022-1-01"));
+        assert!(!is_iso_date("01/02/2022"));
+    }
+}
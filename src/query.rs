@@ -0,0 +1,189 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::word_picker::HardModeWordPicker;
+use crate::NUM_LETTERS;
+
+/// Symbols for a position that can hold any letter
+pub const ANY_LETTER: [char; 2] = ['?', '_'];
+
+/// A crossword-style word search such as `c?a?e -rt +s`
+///
+/// Letters are placed by position with `?` for any letter, `-` lists letters the word must not contain and `+` letters it must contain.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Pattern {
+    pub positions: [Option<char>; NUM_LETTERS],
+    pub excluded: Vec<char>,
+    pub required: Vec<char>,
+}
+
+/// Why a pattern could not be parsed
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PatternError {
+    /// The positional part does not have [NUM_LETTERS] symbols
+    WrongLength(usize),
+    IllegalCharacter(char),
+    /// More than one positional part was given
+    MultiplePositions(String),
+    /// A letter is both excluded and required (or placed)
+    Conflict(char),
+}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatternError::WrongLength(length) => write!(
+                f,
+                "pattern has {} letters, expected {}",
+                length, NUM_LETTERS
+            ),
+            PatternError::IllegalCharacter(c) => write!(
+                f,
+                "illegal character {:?}, expected letters or {}",
+                c, ANY_LETTER[0]
+            ),
+            PatternError::MultiplePositions(part) => {
+                write!(f, "unexpected second pattern {:?}", part)
+            }
+            PatternError::Conflict(letter) => {
+                write!(f, "{:?} is both excluded and required", letter)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+/// Lowercase letters of a `-`/`+` list
+fn parse_letters(letters: &str) -> Result<Vec<char>, PatternError> {
+    letters
+        .chars()
+        .map(|letter| {
+            if letter.is_ascii_alphabetic() {
+                Ok(letter.to_ascii_lowercase())
+            } else {
+                Err(PatternError::IllegalCharacter(letter))
+            }
+        })
+        .collect()
+}
+
+impl FromStr for Pattern {
+    type Err = PatternError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut pattern = Pattern::default();
+        let mut has_positions = false;
+        for part in input.split_whitespace() {
+            if let Some(letters) = part.strip_prefix('-') {
+                pattern.excluded.extend(parse_letters(letters)?);
+            } else if let Some(letters) = part.strip_prefix('+') {
+                pattern.required.extend(parse_letters(letters)?);
+            } else if has_positions {
+                return Err(PatternError::MultiplePositions(part.to_string()));
+            } else {
+                has_positions = true;
+                let symbols: Vec<_> = part.chars().collect();
+                if symbols.len() != NUM_LETTERS {
+                    return Err(PatternError::WrongLength(symbols.len()));
+                }
+                for (position, symbol) in pattern.positions.iter_mut().zip(symbols) {
+                    *position = if ANY_LETTER.contains(&symbol) {
+                        None
+                    } else if symbol.is_ascii_alphabetic() {
+                        Some(symbol.to_ascii_lowercase())
+                    } else {
+                        return Err(PatternError::IllegalCharacter(symbol));
+                    };
+                }
+            }
+        }
+
+        let mut wanted = pattern.positions.iter().flatten().chain(&pattern.required);
+        if let Some(&letter) = wanted.find(|letter| pattern.excluded.contains(letter)) {
+            return Err(PatternError::Conflict(letter));
+        }
+
+        Ok(pattern)
+    }
+}
+
+impl Pattern {
+    /// Remove the words that do not match the pattern
+    pub fn apply(&self, word_picker: &mut HardModeWordPicker) {
+        for (position, letter) in self.positions.iter().enumerate() {
+            if let Some(letter) = *letter {
+                word_picker.remove_words_without_letter_in_position(letter, position);
+            }
+        }
+        for &letter in &self.excluded {
+            word_picker.remove_words_containing(letter);
+        }
+        for &letter in &self.required {
+            word_picker.remove_words_not_containing(letter);
+        }
+    }
+
+    /// The words of a dictionary matching the pattern, alphabetically
+    pub fn matches<S: AsRef<str>>(&self, word_list: &[S]) -> Vec<String> {
+        let mut word_picker = HardModeWordPicker::new(word_list.iter().map(AsRef::as_ref));
+        self.apply(&mut word_picker);
+        let mut matches = word_picker.remaining_words().to_vec();
+        matches.sort_unstable();
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns_place_exclude_and_require_letters() {
+        let pattern: Pattern = "C?a_e -rT +s".parse().unwrap();
+
+        assert_eq!(
+            pattern.positions,
+            [Some('c'), None, Some('a'), None, Some('e')]
+        );
+        assert_eq!(pattern.excluded, ['r', 't']);
+        assert_eq!(pattern.required, ['s']);
+    }
+
+    #[test]
+    fn letter_lists_alone_are_a_pattern() {
+        let pattern: Pattern = "+ae -s".parse().unwrap();
+
+        assert_eq!(pattern.positions, [None; NUM_LETTERS]);
+        assert_eq!(pattern.required, ['a', 'e']);
+    }
+
+    #[test]
+    fn malformed_patterns_are_rejected() {
+        assert_eq!("c?a?".parse::<Pattern>(), Err(PatternError::WrongLength(4)));
+        assert_eq!(
+            "c?a*e".parse::<Pattern>(),
+            Err(PatternError::IllegalCharacter('*'))
+        );
+        assert_eq!(
+            "c?a?e ?????".parse::<Pattern>(),
+            Err(PatternError::MultiplePositions("?????".into()))
+        );
+        assert_eq!(
+            "c?a?e -c".parse::<Pattern>(),
+            Err(PatternError::Conflict('c'))
+        );
+        assert_eq!("+s -s".parse::<Pattern>(), Err(PatternError::Conflict('s')));
+    }
+
+    #[test]
+    fn matches_are_sorted() {
+        let pattern: Pattern = "??a?e -r +s".parse().unwrap();
+
+        assert_eq!(
+            pattern.matches(&["slate", "crane", "shake", "skate", "share"]),
+            ["shake", "skate", "slate"]
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::constraints::Constraints;
+use crate::word_picker::{HardModeWordPicker, ALPHABET};
+
+/// A ranked potential solution
+#[derive(Debug, Clone, Serialize)]
+pub struct Suggestion {
+    pub word: String,
+    /// Number of distinct letters, the picker ranks words with more of them first (unless it has priors)
+    pub score: usize,
+    /// Chance of being the solution, the picker ranks likelier words first when it has priors (otherwise all are equal)
+    pub probability: f64,
+}
+
+/// Machine-readable summary of the game state after a turn
+#[derive(Debug, Clone, Serialize)]
+pub struct TurnReport {
+    /// One-based turn number within the current game
+    pub turn: usize,
+    /// How many potential solutions remain
+    pub remaining: usize,
+    /// Best guesses, best first
+    pub suggestions: Vec<Suggestion>,
+    /// How many remaining words contain each letter (letters found in no words are omitted)
+    pub letter_frequencies: BTreeMap<char, usize>,
+    pub constraints: Constraints,
+}
+
+/// The picker's best guesses, best first
+pub fn suggestions(word_picker: &HardModeWordPicker) -> Vec<Suggestion> {
+    word_picker
+        .top_10_scored_words()
+        .map(|(word, score)| Suggestion {
+            word: word.clone(),
+            score,
+            probability: word_picker.probability(word),
+        })
+        .collect()
+}
+
+impl TurnReport {
+    pub fn new(turn: usize, word_picker: &HardModeWordPicker) -> Self {
+        let letter_frequencies = ALPHABET
+            .iter()
+            .copied()
+            .zip(word_picker.letter_frequencies())
+            .filter(|&(_, freq)| freq > 0)
+            .collect();
+
+        Self {
+            turn,
+            remaining: word_picker.remaining(),
+            suggestions: suggestions(word_picker),
+            letter_frequencies,
+            constraints: word_picker.constraints().clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn ranked(suggestions: &[Suggestion]) -> Vec<(&str, usize, f64)> {
+        suggestions
+            .iter()
+            .map(|suggestion| {
+                (
+                    suggestion.word.as_str(),
+                    suggestion.score,
+                    suggestion.probability,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn suggestions_are_ranked_by_distinct_letters() {
+        let word_picker = HardModeWordPicker::new(["geese", "crane"]);
+
+        assert_eq!(
+            ranked(&suggestions(&word_picker)),
+            [("crane", 5, 0.5), ("geese", 3, 0.5)]
+        );
+    }
+
+    #[test]
+    fn priors_reorder_the_suggestions() {
+        let priors = [("crane", 1.0), ("geese", 3.0)]
+            .into_iter()
+            .map(|(word, weight)| (word.to_string(), weight))
+            .collect();
+        let word_picker = HardModeWordPicker::new(["geese", "crane"]).with_priors(Arc::new(priors));
+
+        assert_eq!(
+            ranked(&suggestions(&word_picker)),
+            [("geese", 3, 0.75), ("crane", 5, 0.25)]
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use std::io::{stdin, stdout, BufRead, Write};
+use std::sync::Arc;
+
+use log::{debug, trace, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use wordle_helper::dictionary::Priors;
+use wordle_helper::report::{suggestions, TurnReport};
+use wordle_helper::solver::{format_turn, parse_turn, Solver, SolverError};
+
+/// Request could not be parsed as JSON
+const PARSE_ERROR: i64 = -32700;
+/// JSON was not a valid request object
+const INVALID_REQUEST: i64 = -32600;
+/// Unknown method name
+const METHOD_NOT_FOUND: i64 = -32601;
+/// Params were missing or of the wrong shape
+const INVALID_PARAMS: i64 = -32602;
+/// Guess is not a legal word
+const INVALID_WORD: i64 = -32000;
+/// Reply is not made of reply symbols
+const INVALID_REPLY: i64 = -32001;
+/// Turn would leave no potential solutions
+const CONTRADICTORY_TURN: i64 = -32002;
+/// No turn to undo
+const NOTHING_TO_UNDO: i64 = -32003;
+
+#[derive(Deserialize)]
+struct Request {
+    jsonrpc: String,
+    /// Requests without an id are notifications and get no response
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Serialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<SolverError> for RpcError {
+    fn from(error: SolverError) -> Self {
+        let code = match error {
+            SolverError::InvalidWord(_) => INVALID_WORD,
+            SolverError::InvalidReply(_) => INVALID_REPLY,
+            SolverError::ContradictoryTurn => CONTRADICTORY_TURN,
+            SolverError::NothingToUndo => NOTHING_TO_UNDO,
+        };
+
+        Self::new(code, error.to_string())
+    }
+}
+
+#[derive(Deserialize)]
+struct TurnParams {
+    guess: String,
+    reply: String,
+}
+
+#[derive(Deserialize)]
+struct GuessParams {
+    guess: String,
+}
+
+/// Serves a single game as newline-delimited JSON-RPC 2.0 on stdin/stdout
+pub struct RpcServer {
+    solver: Solver,
+}
+
+impl RpcServer {
+    pub fn new(word_list: Vec<String>) -> Self {
+        Self {
+            solver: Solver::new(word_list),
+        }
+    }
+
+    /// Weigh the words by their prior probability of being the solution
+    pub fn with_priors(mut self, priors: Arc<Priors>) -> Self {
+        self.solver = self.solver.with_priors(priors);
+
+        self
+    }
+
+    /// Answer requests until stdin is closed
+    pub fn run(&mut self) {
+        debug!("starting json-rpc server");
+        for line in stdin().lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(error) => {
+                    warn!("failed to read request: {}", error);
+                    break;
+                }
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            trace!("received request {}", line);
+
+            if let Some(response) = self.handle(&line) {
+                let mut stdout = stdout().lock();
+                writeln!(stdout, "{}", response).expect("failed to write response to stdout");
+                stdout.flush().expect("failed to flush response to stdout");
+            }
+        }
+        debug!("stdin closed, stopping json-rpc server");
+    }
+
+    /// Handle one line of input, returning the response to write (if any)
+    fn handle(&mut self, line: &str) -> Option<Value> {
+        let request: Value = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(error) => {
+                return Some(error_response(
+                    Value::Null,
+                    RpcError::new(PARSE_ERROR, error.to_string()),
+                ))
+            }
+        };
+        // Invalid requests still echo the id when it is usable, only unparsable ones get null
+        let id = request_id(&request);
+        let request = match serde_json::from_value::<Request>(request) {
+            Ok(request) if request.jsonrpc == "2.0" => request,
+            Ok(_) => {
+                return Some(error_response(
+                    id,
+                    RpcError::new(INVALID_REQUEST, "expected jsonrpc version 2.0"),
+                ))
+            }
+            Err(error) => {
+                return Some(error_response(
+                    id,
+                    RpcError::new(INVALID_REQUEST, error.to_string()),
+                ))
+            }
+        };
+
+        let result = self.dispatch(&request.method, request.params);
+        let id = request.id?;
+
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => error_response(id, error),
+        })
+    }
+
+    fn dispatch(&mut self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "newGame" => {
+                self.solver.reset();
+                Ok(json!({ "remaining": self.solver.word_picker().remaining() }))
+            }
+            "applyTurn" => {
+                let TurnParams { guess, reply } = parse_params(params)?;
+                self.solver.apply_turn(parse_turn(&guess, &reply)?)?;
+                Ok(to_value(&self.report()))
+            }
+            "suggest" => Ok(to_value(&suggestions(self.solver.word_picker()))),
+            "candidates" => Ok(to_value(&self.solver.word_picker().remaining_words())),
+            "undo" => {
+                let (guess, reply) = format_turn(&self.solver.undo()?);
+                Ok(json!({
+                    "undone": { "guess": guess, "reply": reply },
+                    "report": self.report(),
+                }))
+            }
+            "evaluateGuess" => {
+                let GuessParams { guess } = parse_params(params)?;
+                Ok(to_value(&self.solver.evaluate_guess(&guess)?))
+            }
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("unknown method {:?}", method),
+            )),
+        }
+    }
+
+    fn report(&self) -> TurnReport {
+        TurnReport::new(self.solver.turns().len(), self.solver.word_picker())
+    }
+}
+
+fn parse_params<T: for<'de> Deserialize<'de>>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|error| RpcError::new(INVALID_PARAMS, error.to_string()))
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).expect("rpc results are always serializable")
+}
+
+/// The id of a request object if it is a string or number, otherwise null
+fn request_id(request: &Value) -> Value {
+    match request.get("id") {
+        Some(id @ (Value::String(_) | Value::Number(_))) => id.clone(),
+        _ => Value::Null,
+    }
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": error })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handle(line: &str) -> Value {
+        RpcServer::new(vec![String::from("crane")])
+            .handle(line)
+            .expect("requests with an id get a response")
+    }
+
+    #[test]
+    fn invalid_requests_echo_their_id() {
+        let response = handle(r#"{"jsonrpc":"1.0","id":7,"method":"suggest"}"#);
+        assert_eq!(response["id"], json!(7));
+        assert_eq!(response["error"]["code"], json!(INVALID_REQUEST));
+
+        let response = handle(r#"{"jsonrpc":"2.0","id":"a","method":3}"#);
+        assert_eq!(response["id"], json!("a"));
+    }
+
+    #[test]
+    fn unusable_ids_are_null() {
+        assert_eq!(handle("not json")["id"], Value::Null);
+        assert_eq!(handle(r#"{"id":{"nested":1}}"#)["id"], Value::Null);
+    }
+}
@@ -0,0 +1,393 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::io::{Cursor, Read};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server, StatusCode};
+use wordle_helper::dictionary::Priors;
+use wordle_helper::report::{suggestions, Suggestion, TurnReport};
+use wordle_helper::solver::{parse_turn, Solver, SolverError};
+
+type JsonResponse = Response<Cursor<Vec<u8>>>;
+
+/// Most sessions kept at once, the least recently used one is dropped to make room
+const MAX_SESSIONS: usize = 1024;
+/// Sessions unused for this long are dropped
+const SESSION_TTL: Duration = Duration::from_secs(60 * 60);
+/// Largest request body read, anything longer is rejected
+const MAX_BODY_BYTES: u64 = 64 * 1024;
+/// Session ids stay below 2^53 so JavaScript clients can read them as numbers
+const SESSION_ID_MASK: u64 = (1 << 53) - 1;
+
+/// A game in progress and when it was last used
+struct Session {
+    solver: Solver,
+    last_used: Instant,
+}
+
+/// A turn as sent over HTTP
+#[derive(Deserialize)]
+struct TurnBody {
+    guess: String,
+    reply: String,
+}
+
+/// Body of a stateless solve request
+#[derive(Deserialize)]
+struct SolveBody {
+    turns: Vec<TurnBody>,
+}
+
+/// Body of a guess evaluation request
+#[derive(Deserialize)]
+struct EvaluateBody {
+    guess: String,
+}
+
+/// Result of a stateless solve request
+#[derive(Serialize)]
+struct SolveResult<'a> {
+    remaining: usize,
+    candidates: &'a [String],
+    suggestions: Vec<Suggestion>,
+}
+
+/// Result of creating a session
+#[derive(Serialize)]
+struct CreatedSession {
+    id: u64,
+    report: TurnReport,
+}
+
+/// Failures while handling a request, each mapped to an HTTP status
+enum HttpError {
+    NotFound,
+    MethodNotAllowed,
+    PayloadTooLarge,
+    BadRequest(String),
+    Solver(SolverError),
+}
+
+impl From<SolverError> for HttpError {
+    fn from(error: SolverError) -> Self {
+        HttpError::Solver(error)
+    }
+}
+
+impl HttpError {
+    fn into_response(self) -> JsonResponse {
+        let (status, kind, message) = match self {
+            HttpError::NotFound => (404, "notFound", String::from("no such resource")),
+            HttpError::MethodNotAllowed => (
+                405,
+                "methodNotAllowed",
+                String::from("method not allowed for this resource"),
+            ),
+            HttpError::PayloadTooLarge => (
+                413,
+                "payloadTooLarge",
+                format!("body is larger than {} bytes", MAX_BODY_BYTES),
+            ),
+            HttpError::BadRequest(message) => (400, "badRequest", message),
+            HttpError::Solver(error) => {
+                let (status, kind) = match error {
+                    SolverError::InvalidWord(_) => (400, "invalidWord"),
+                    SolverError::InvalidReply(_) => (400, "invalidReply"),
+                    SolverError::ContradictoryTurn => (422, "contradictoryTurn"),
+                    SolverError::NothingToUndo => (409, "nothingToUndo"),
+                };
+                (status, kind, error.to_string())
+            }
+        };
+
+        json_response(
+            status,
+            &json!({ "error": { "kind": kind, "message": message } }),
+        )
+    }
+}
+
+/// Serves the solver over HTTP, both statelessly and as sessions identified by id
+pub struct HttpServer {
+    /// Every game starts from a copy of this solver
+    solver: Solver,
+    sessions: HashMap<u64, Session>,
+    /// Counts the sessions created, hashed with [HttpServer::session_keys] into ids that cannot be guessed
+    next_session_id: u64,
+    session_keys: RandomState,
+}
+
+impl HttpServer {
+    pub fn new(word_list: Vec<String>) -> Self {
+        Self {
+            solver: Solver::new(word_list),
+            sessions: HashMap::new(),
+            next_session_id: 1,
+            session_keys: RandomState::new(),
+        }
+    }
+
+    /// Weigh the words by their prior probability of being the solution
+    pub fn with_priors(mut self, priors: Arc<Priors>) -> Self {
+        self.solver = self.solver.with_priors(priors);
+
+        self
+    }
+
+    /// Listen on an address and answer requests until the process exits
+    pub fn serve(&mut self, address: &str) -> Result<(), String> {
+        let server = Server::http(address).map_err(|error| error.to_string())?;
+        info!("serving solver on http://{}", server.server_addr());
+
+        for mut request in server.incoming_requests() {
+            debug!("{} {}", request.method(), request.url());
+            let response = self
+                .handle(&mut request)
+                .unwrap_or_else(HttpError::into_response);
+            if let Err(error) = request.respond(response) {
+                warn!("failed to send response: {}", error);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle(&mut self, request: &mut Request) -> Result<JsonResponse, HttpError> {
+        let url = request.url().to_string();
+        let path = url.split('?').next().unwrap_or_default();
+        let segments: Vec<_> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let method = request.method().clone();
+        let body = read_body(request.as_reader())?;
+        self.drop_expired_sessions();
+
+        match (method, segments.as_slice()) {
+            (Method::Post, ["solve"]) => {
+                let SolveBody { turns } = parse_json(&body)?;
+                let mut solver = self.solver.clone();
+                for TurnBody { guess, reply } in turns {
+                    solver.apply_turn(parse_turn(&guess, &reply)?)?;
+                }
+                let word_picker = solver.word_picker();
+
+                Ok(json_response(
+                    200,
+                    &SolveResult {
+                        remaining: word_picker.remaining(),
+                        candidates: word_picker.remaining_words(),
+                        suggestions: suggestions(word_picker),
+                    },
+                ))
+            }
+            (Method::Post, ["sessions"]) => {
+                let id = self.new_session_id();
+                let solver = self.solver.clone();
+                let report = session_report(&solver);
+                if self.sessions.len() >= MAX_SESSIONS {
+                    self.drop_least_recently_used_session();
+                }
+                self.sessions.insert(
+                    id,
+                    Session {
+                        solver,
+                        last_used: Instant::now(),
+                    },
+                );
+                debug!("created session {}", id);
+
+                Ok(json_response(201, &CreatedSession { id, report }))
+            }
+            (_, ["solve"]) | (_, ["sessions"]) => Err(HttpError::MethodNotAllowed),
+            (method, ["sessions", id, rest @ ..]) => {
+                let id: u64 = id.parse().map_err(|_| HttpError::NotFound)?;
+                if method == Method::Delete && rest.is_empty() {
+                    self.sessions.remove(&id).ok_or(HttpError::NotFound)?;
+                    debug!("deleted session {}", id);
+                    return Ok(json_response(200, &json!({ "id": id })));
+                }
+                let session = self.sessions.get_mut(&id).ok_or(HttpError::NotFound)?;
+                session.last_used = Instant::now();
+
+                handle_session(&mut session.solver, &method, rest, &body)
+            }
+            _ => Err(HttpError::NotFound),
+        }
+    }
+
+    /// An unused id that other clients cannot guess from their own ids
+    fn new_session_id(&mut self) -> u64 {
+        loop {
+            let id = self.session_keys.hash_one(self.next_session_id) & SESSION_ID_MASK;
+            self.next_session_id += 1;
+            if !self.sessions.contains_key(&id) {
+                return id;
+            }
+        }
+    }
+
+    fn drop_expired_sessions(&mut self) {
+        let before = self.sessions.len();
+        self.sessions
+            .retain(|_, session| session.last_used.elapsed() < SESSION_TTL);
+        if self.sessions.len() < before {
+            debug!("dropped {} expired sessions", before - self.sessions.len());
+        }
+    }
+
+    fn drop_least_recently_used_session(&mut self) {
+        let oldest = self
+            .sessions
+            .iter()
+            .min_by_key(|(_, session)| session.last_used)
+            .map(|(&id, _)| id);
+        if let Some(id) = oldest {
+            self.sessions.remove(&id);
+            debug!(
+                "dropped session {} to stay under {} sessions",
+                id, MAX_SESSIONS
+            );
+        }
+    }
+}
+
+/// Handle requests under `/sessions/{id}`
+fn handle_session(
+    solver: &mut Solver,
+    method: &Method,
+    path: &[&str],
+    body: &str,
+) -> Result<JsonResponse, HttpError> {
+    match (method, path) {
+        (Method::Get, []) => Ok(json_response(200, &session_report(solver))),
+        (Method::Get, ["candidates"]) => {
+            Ok(json_response(200, &solver.word_picker().remaining_words()))
+        }
+        (Method::Get, ["suggestions"]) => {
+            Ok(json_response(200, &suggestions(solver.word_picker())))
+        }
+        (Method::Post, ["turns"]) => {
+            let TurnBody { guess, reply } = parse_json(body)?;
+            solver.apply_turn(parse_turn(&guess, &reply)?)?;
+            Ok(json_response(200, &session_report(solver)))
+        }
+        (Method::Post, ["undo"]) => {
+            solver.undo()?;
+            Ok(json_response(200, &session_report(solver)))
+        }
+        (Method::Post, ["reset"]) => {
+            solver.reset();
+            Ok(json_response(200, &session_report(solver)))
+        }
+        (Method::Post, ["evaluate"]) => {
+            let EvaluateBody { guess } = parse_json(body)?;
+            Ok(json_response(200, &solver.evaluate_guess(&guess)?))
+        }
+        (_, [] | ["candidates" | "suggestions" | "turns" | "undo" | "reset" | "evaluate"]) => {
+            Err(HttpError::MethodNotAllowed)
+        }
+        _ => Err(HttpError::NotFound),
+    }
+}
+
+fn session_report(solver: &Solver) -> TurnReport {
+    TurnReport::new(solver.turns().len(), solver.word_picker())
+}
+
+/// Read a body of at most [MAX_BODY_BYTES]
+fn read_body<R: Read>(reader: R) -> Result<String, HttpError> {
+    let mut body = Vec::new();
+    // One byte more than allowed tells a body at the limit from a longer one
+    reader
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_end(&mut body)
+        .map_err(|error| HttpError::BadRequest(format!("unable to read body: {}", error)))?;
+    if body.len() as u64 > MAX_BODY_BYTES {
+        return Err(HttpError::PayloadTooLarge);
+    }
+
+    String::from_utf8(body)
+        .map_err(|error| HttpError::BadRequest(format!("body is not valid UTF-8: {}", error)))
+}
+
+fn parse_json<T: for<'de> Deserialize<'de>>(body: &str) -> Result<T, HttpError> {
+    serde_json::from_str(body)
+        .map_err(|error| HttpError::BadRequest(format!("invalid json body: {}", error)))
+}
+
+fn json_response<T: Serialize + ?Sized>(status: u16, value: &T) -> JsonResponse {
+    let body = serde_json::to_vec(value).expect("http responses are always serializable");
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("content type header is valid");
+
+    Response::from_data(body)
+        .with_status_code(StatusCode(status))
+        .with_header(content_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server_with_sessions(ages: &[u64]) -> HttpServer {
+        let mut server = HttpServer::new(vec![String::from("crane")]);
+        let now = Instant::now();
+        for (id, &age) in ages.iter().enumerate() {
+            server.sessions.insert(
+                id as u64,
+                Session {
+                    solver: server.solver.clone(),
+                    last_used: now - Duration::from_secs(age),
+                },
+            );
+        }
+
+        server
+    }
+
+    #[test]
+    fn session_ids_are_not_sequential() {
+        let mut server = server_with_sessions(&[]);
+        let ids: Vec<_> = (0..3).map(|_| server.new_session_id()).collect();
+
+        assert!(ids.windows(2).all(|pair| pair[1] != pair[0] + 1));
+        assert!(ids.iter().all(|&id| id <= SESSION_ID_MASK));
+    }
+
+    #[test]
+    fn bodies_over_the_limit_are_rejected() {
+        let body = vec![b' '; MAX_BODY_BYTES as usize];
+        assert_eq!(
+            read_body(&body[..]).ok().map(|body| body.len()),
+            Some(body.len())
+        );
+
+        let body = vec![b' '; MAX_BODY_BYTES as usize + 1];
+        assert!(matches!(
+            read_body(&body[..]),
+            Err(HttpError::PayloadTooLarge)
+        ));
+    }
+
+    #[test]
+    fn expired_sessions_are_dropped() {
+        let mut server = server_with_sessions(&[0, SESSION_TTL.as_secs() + 1]);
+        server.drop_expired_sessions();
+
+        assert!(server.sessions.contains_key(&0));
+        assert!(!server.sessions.contains_key(&1));
+    }
+
+    #[test]
+    fn least_recently_used_session_is_dropped() {
+        let mut server = server_with_sessions(&[10, 30, 20]);
+        server.drop_least_recently_used_session();
+
+        let mut ids: Vec<_> = server.sessions.keys().copied().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, [0, 2]);
+    }
+}
@@ -0,0 +1,149 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::sync::Arc;
+
+use wordle_helper::dictionary::Priors;
+use wordle_helper::simulation::{sample, SimulationReport};
+use wordle_helper::strategy::Strategy;
+use wordle_helper::tournament::{AnswerDifference, Contestant, TournamentReport};
+use wordle_helper::MAX_GUESSES;
+
+/// Separator between words in a listing
+const WORD_SEPARATOR: &str = ", ";
+
+/// Play every answer (or an evenly spaced sample of them) and print how the strategy performed
+pub fn run_simulation(
+    word_list: &[String],
+    answers: &[String],
+    sample_size: Option<usize>,
+    worst_len: usize,
+    strategy: Strategy,
+    opener: Option<&str>,
+    priors: Option<Arc<Priors>>,
+) {
+    let answers = match sample_size {
+        Some(sample_size) => sample(answers, sample_size),
+        None => answers.to_vec(),
+    };
+    let report = SimulationReport::run(word_list, &answers, strategy, opener, priors);
+
+    println!("Simulated {} games with {}", report.games.len(), strategy);
+    println!("Average guesses: {:.3}", report.average_guesses());
+    println!("Distribution:");
+    for (index, games) in report.distribution().into_iter().enumerate() {
+        println!("  {}: {}", index + 1, games);
+    }
+
+    let failures: Vec<_> = report.failures().collect();
+    println!("  X: {}", failures.len());
+
+    let worst_cases = report
+        .worst_cases(worst_len)
+        .into_iter()
+        .map(|game| format!("{} ({})", game.answer, game.guesses.len()))
+        .collect::<Vec<_>>();
+    println!("Worst cases: {}", worst_cases.join(WORD_SEPARATOR));
+
+    if !failures.is_empty() {
+        println!("Failed within {} guesses:", MAX_GUESSES);
+        for game in failures {
+            if game.solved() {
+                println!("  {}: {}", game.answer, game.guesses.join(WORD_SEPARATOR));
+            } else {
+                println!("  {}: not in dictionary", game.answer);
+            }
+        }
+    }
+}
+
+/// Play every contestant over the same answers, print a comparison and optionally export per-answer results
+pub fn run_tournament(
+    word_list: &[String],
+    answers: &[String],
+    sample_size: Option<usize>,
+    contestants: Vec<Contestant>,
+    list_len: usize,
+    csv_path: Option<&Path>,
+    priors: Option<Arc<Priors>>,
+) -> std::io::Result<()> {
+    let answers = match sample_size {
+        Some(sample_size) => sample(answers, sample_size),
+        None => answers.to_vec(),
+    };
+    let report = TournamentReport::run(word_list, &answers, contestants, priors);
+    let names: Vec<_> = report.contestants.iter().map(Contestant::name).collect();
+    let name_width = names.iter().map(String::len).max().unwrap_or(0);
+
+    println!("Played {} answers", answers.len());
+    println!(
+        "{:name_width$}  {:>6}  {:>6}  {:>6}  {:>8}",
+        "contestant",
+        "mean",
+        "median",
+        "p95",
+        "failures",
+        name_width = name_width
+    );
+    for (index, name) in names.iter().enumerate() {
+        let standing = report.standing(index);
+        println!(
+            "{:name_width$}  {:>6.3}  {:>6}  {:>6}  {:>8}",
+            name,
+            standing.mean,
+            standing.median,
+            standing.p95,
+            standing.failures,
+            name_width = name_width
+        );
+    }
+
+    for a in 0..names.len() {
+        for b in (a + 1)..names.len() {
+            let head_to_head = report.head_to_head(a, b);
+            println!(
+                "\n{} vs {}: {} better, {} better, {} tied",
+                names[a],
+                names[b],
+                head_to_head.a_better.len(),
+                head_to_head.b_better.len(),
+                head_to_head.ties
+            );
+            print_differences(&names[a], &head_to_head.a_better, list_len);
+            print_differences(&names[b], &head_to_head.b_better, list_len);
+        }
+    }
+
+    if let Some(csv_path) = csv_path {
+        report.write_csv(BufWriter::new(File::create(csv_path)?))?;
+        println!("\nWrote per-answer results to {}", csv_path.display());
+    }
+
+    Ok(())
+}
+
+/// List the answers a contestant solved in fewer guesses, biggest differences first
+fn print_differences(name: &str, differences: &[AnswerDifference], list_len: usize) {
+    if differences.is_empty() {
+        return;
+    }
+    let mut differences = differences.to_vec();
+    differences.sort_by_key(|d| std::cmp::Reverse(d.a_guesses.abs_diff(d.b_guesses)));
+    let listed: Vec<_> = differences
+        .iter()
+        .take(list_len)
+        .map(|d| format!("{} ({}-{})", d.answer, d.a_guesses, d.b_guesses))
+        .collect();
+    let more = differences.len().saturating_sub(list_len);
+
+    println!(
+        "  {} better on: {}{}",
+        name,
+        listed.join(WORD_SEPARATOR),
+        if more > 0 {
+            format!(" and {} more", more)
+        } else {
+            String::new()
+        }
+    );
+}
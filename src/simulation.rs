@@ -0,0 +1,245 @@
+use std::sync::Arc;
+
+use log::{debug, trace};
+
+use crate::dictionary::Priors;
+use crate::game::{make_turn, response_for, valid_words, Reply};
+use crate::opening_book;
+use crate::strategy::Strategy;
+use crate::word_picker::HardModeWordPicker;
+use crate::MAX_GUESSES;
+
+/// The guesses made while playing against a single answer
+#[derive(Debug, Clone)]
+pub struct GameResult {
+    pub answer: String,
+    /// Every guess in order, the last one is the answer when solved
+    pub guesses: Vec<String>,
+}
+
+impl GameResult {
+    /// If the answer was eventually guessed (it can be missing from the dictionary)
+    pub fn solved(&self) -> bool {
+        self.guesses.last() == Some(&self.answer)
+    }
+
+    /// If the answer was guessed within [MAX_GUESSES]
+    pub fn won(&self) -> bool {
+        self.solved() && self.guesses.len() <= MAX_GUESSES
+    }
+}
+
+/// Play the strategy's guess each turn until the answer is found (or no words remain), starting with `opener` if given
+///
+/// Play continues past [MAX_GUESSES] so that failures still report how many guesses they needed.
+/// `answer` must be lowercase ascii of the right length, see [valid_words].
+pub fn play(
+    word_picker: &HardModeWordPicker,
+    answer: &str,
+    strategy: Strategy,
+    opener: Option<&str>,
+) -> GameResult {
+    let mut word_picker = word_picker.clone();
+    let mut guesses = Vec::new();
+    let mut next_guess = opener
+        .map(String::from)
+        .or_else(|| strategy.pick(&word_picker).cloned());
+
+    while let Some(guess) = next_guess {
+        let response = response_for(&guess, answer);
+        trace!("{} guessed {} for {}", guesses.len() + 1, guess, answer);
+        guesses.push(guess.clone());
+        if response.iter().all(|&reply| reply == Reply::Success) {
+            break;
+        }
+        word_picker.take_turn(make_turn(&guess, &response));
+        next_guess = strategy.pick(&word_picker).cloned();
+    }
+
+    GameResult {
+        answer: answer.to_string(),
+        guesses,
+    }
+}
+
+/// Results of playing the strategy against a set of answers
+#[derive(Debug, Clone)]
+pub struct SimulationReport {
+    pub games: Vec<GameResult>,
+}
+
+impl SimulationReport {
+    /// Play every answer against a dictionary with a strategy, starting with `opener` if given
+    ///
+    /// With `priors` the strategy weighs the words by their prior probability, see [HardModeWordPicker::with_priors].
+    pub fn run<S: AsRef<str>>(
+        word_list: &[String],
+        answers: &[S],
+        strategy: Strategy,
+        opener: Option<&str>,
+        priors: Option<Arc<Priors>>,
+    ) -> Self {
+        debug!(
+            "simulating {} answers against {} words with {}",
+            answers.len(),
+            word_list.len(),
+            strategy
+        );
+        // Answers go straight into response_for, which only takes lowercase words of the right length
+        let answers = valid_words(answers);
+        let mut word_picker = HardModeWordPicker::new(word_list);
+        // The book was computed without weights
+        let in_book = priors.is_none() && opening_book::applies_to(word_list);
+        if let Some(priors) = priors {
+            word_picker = word_picker.with_priors(priors);
+        }
+        // The first guess never changes so only pick it once
+        let opener = opener
+            .map(String::from)
+            .or_else(|| opening_book::next_guess(strategy, in_book, &word_picker, &[]));
+        let games = answers
+            .iter()
+            .map(|answer| play(&word_picker, answer, strategy, opener.as_deref()))
+            .collect();
+
+        Self { games }
+    }
+
+    /// Average number of guesses over the solved games
+    pub fn average_guesses(&self) -> f64 {
+        let solved = self.games.iter().filter(|game| game.solved());
+        let (count, total) = solved.fold((0, 0), |(count, total), game| {
+            (count + 1, total + game.guesses.len())
+        });
+
+        if count == 0 {
+            0.0
+        } else {
+            total as f64 / count as f64
+        }
+    }
+
+    /// Guesses needed by each solved game, fewest first
+    fn sorted_guess_counts(&self) -> Vec<usize> {
+        let mut counts: Vec<_> = self
+            .games
+            .iter()
+            .filter(|game| game.solved())
+            .map(|game| game.guesses.len())
+            .collect();
+        counts.sort_unstable();
+
+        counts
+    }
+
+    /// Guesses needed by the game at a percentile (nearest rank, `0.0..=1.0`) of the solved games
+    pub fn percentile(&self, percentile: f64) -> usize {
+        let counts = self.sorted_guess_counts();
+        if counts.is_empty() {
+            return 0;
+        }
+        let rank = (percentile * counts.len() as f64).ceil() as usize;
+
+        counts[rank.clamp(1, counts.len()) - 1]
+    }
+
+    /// How many games were won in each number of guesses, index 0 is a win in 1 guess
+    pub fn distribution(&self) -> [usize; MAX_GUESSES] {
+        let mut distribution = [0; MAX_GUESSES];
+        for game in self.games.iter().filter(|game| game.won()) {
+            distribution[game.guesses.len() - 1] += 1;
+        }
+
+        distribution
+    }
+
+    /// The games that needed the most guesses, most first
+    pub fn worst_cases(&self, count: usize) -> Vec<&GameResult> {
+        let mut games: Vec<_> = self.games.iter().filter(|game| game.solved()).collect();
+        games.sort_by_key(|game| std::cmp::Reverse(game.guesses.len()));
+        games.truncate(count);
+
+        games
+    }
+
+    /// The games not won within [MAX_GUESSES]
+    pub fn failures(&self) -> impl Iterator<Item = &GameResult> {
+        self.games.iter().filter(|game| !game.won())
+    }
+}
+
+/// Pick `count` answers spread evenly across the list, keeping their order
+pub fn sample<T: Clone>(answers: &[T], count: usize) -> Vec<T> {
+    if count >= answers.len() {
+        return answers.to_vec();
+    }
+
+    (0..count)
+        .map(|index| answers[index * answers.len() / count].clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A game solved in `count` guesses, or never solved if `count` is 0
+    fn game(answer: &str, count: usize) -> GameResult {
+        let mut guesses = vec![String::from("xxxxx"); count.max(1)];
+        if count > 0 {
+            guesses[count - 1] = answer.to_string();
+        }
+
+        GameResult {
+            answer: answer.to_string(),
+            guesses,
+        }
+    }
+
+    #[test]
+    fn percentiles_of_an_odd_count() {
+        let report = SimulationReport {
+            games: vec![
+                game("crane", 3),
+                game("slate", 1),
+                game("trace", 0),
+                game("audio", 2),
+            ],
+        };
+
+        assert_eq!(report.percentile(0.5), 2);
+        assert_eq!(report.percentile(0.95), 3);
+    }
+
+    #[test]
+    fn percentiles_of_an_even_count() {
+        let report = SimulationReport {
+            games: vec![
+                game("crane", 4),
+                game("slate", 1),
+                game("trace", 3),
+                game("audio", 2),
+            ],
+        };
+
+        assert_eq!(report.percentile(0.5), 2);
+        assert_eq!(report.percentile(0.95), 4);
+        assert_eq!(report.percentile(0.0), 1);
+        assert_eq!(SimulationReport { games: vec![] }.percentile(0.5), 0);
+    }
+
+    #[test]
+    fn malformed_answers_are_left_out() {
+        let word_list = vec![String::from("crane"), String::from("slate")];
+        let answers = ["Crane\r", "", "slat", "sl8te", "slate"];
+        let report = SimulationReport::run(&word_list, &answers, Strategy::Picker, None, None);
+
+        let played: Vec<_> = report
+            .games
+            .iter()
+            .map(|game| game.answer.as_str())
+            .collect();
+        assert_eq!(played, ["crane", "slate"]);
+        assert!(report.games.iter().all(GameResult::solved));
+    }
+}
@@ -0,0 +1,206 @@
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+use log::debug;
+
+use crate::dictionary::Priors;
+use crate::evaluation::GuessEvaluation;
+use crate::game::{format_response, make_turn, parse_response, Turn};
+use crate::word_picker::HardModeWordPicker;
+use crate::NUM_LETTERS;
+
+/// Reasons a turn cannot be applied to a game
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SolverError {
+    /// Guess is not a word made of [NUM_LETTERS] ascii letters
+    InvalidWord(String),
+    /// Reply is not made of [NUM_LETTERS] reply symbols
+    InvalidReply(String),
+    /// Turn leaves no potential solutions, it contradicts the turns before it
+    ContradictoryTurn,
+    /// There is no turn to undo
+    NothingToUndo,
+}
+
+impl Display for SolverError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolverError::InvalidWord(word) => write!(
+                f,
+                "invalid word {:?}: expected {} alphabetical characters",
+                word, NUM_LETTERS
+            ),
+            SolverError::InvalidReply(reply) => write!(
+                f,
+                "invalid reply {:?}: expected {} reply symbols",
+                reply, NUM_LETTERS
+            ),
+            SolverError::ContradictoryTurn => {
+                write!(f, "turn contradicts the previous turns, no words remain")
+            }
+            SolverError::NothingToUndo => write!(f, "no turns to undo"),
+        }
+    }
+}
+
+impl std::error::Error for SolverError {}
+
+/// Validate and normalize a guess
+pub fn parse_guess(guess: &str) -> Result<String, SolverError> {
+    let normalized = guess.trim().to_ascii_lowercase();
+    if normalized.len() == NUM_LETTERS && normalized.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(normalized)
+    } else {
+        Err(SolverError::InvalidWord(guess.to_string()))
+    }
+}
+
+/// Build a turn from a guess and a reply written with reply symbols
+pub fn parse_turn(guess: &str, reply: &str) -> Result<Turn, SolverError> {
+    let guess = parse_guess(guess)?;
+    let response =
+        parse_response(reply.trim()).ok_or_else(|| SolverError::InvalidReply(reply.to_string()))?;
+
+    Ok(make_turn(&guess, &response))
+}
+
+/// A single game against a dictionary, with a history of turns that can be undone
+#[derive(Debug, Clone)]
+pub struct Solver {
+    /// The picker before any turn, turns are replayed on it
+    start: HardModeWordPicker,
+    turns: Vec<Turn>,
+    word_picker: HardModeWordPicker,
+}
+
+impl Solver {
+    pub fn new(word_list: Vec<String>) -> Self {
+        let word_picker = HardModeWordPicker::new(&word_list);
+
+        Self {
+            start: word_picker.clone(),
+            turns: Vec::new(),
+            word_picker,
+        }
+    }
+
+    /// Weigh the words by their prior probability of being the solution, see [HardModeWordPicker::with_priors]
+    pub fn with_priors(mut self, priors: Arc<Priors>) -> Self {
+        self.start = self.start.with_priors(priors);
+        self.word_picker = self.start.clone();
+        for turn in &self.turns {
+            self.word_picker.take_turn(turn.clone());
+        }
+
+        self
+    }
+
+    /// Start over with every word in the dictionary
+    pub fn reset(&mut self) {
+        self.turns.clear();
+        self.word_picker = self.start.clone();
+    }
+
+    /// The turns taken so far, oldest first
+    pub fn turns(&self) -> &[Turn] {
+        &self.turns
+    }
+
+    pub fn word_picker(&self) -> &HardModeWordPicker {
+        &self.word_picker
+    }
+
+    /// Apply a turn, rejecting it (and leaving the game untouched) if no words would remain
+    pub fn apply_turn(&mut self, turn: Turn) -> Result<(), SolverError> {
+        let mut word_picker = self.word_picker.clone();
+        word_picker.take_turn(turn.clone());
+        if word_picker.remaining() == 0 {
+            debug!("rejecting contradictory turn {:?}", turn);
+            return Err(SolverError::ContradictoryTurn);
+        }
+
+        self.word_picker = word_picker;
+        self.turns.push(turn);
+
+        Ok(())
+    }
+
+    /// Remove the last turn, replaying the ones before it
+    pub fn undo(&mut self) -> Result<Turn, SolverError> {
+        let turn = self.turns.pop().ok_or(SolverError::NothingToUndo)?;
+        self.word_picker = self.start.clone();
+        for turn in &self.turns {
+            self.word_picker.take_turn(turn.clone());
+        }
+
+        Ok(turn)
+    }
+
+    /// How well a guess would split the remaining potential solutions
+    pub fn evaluate_guess(&self, guess: &str) -> Result<GuessEvaluation, SolverError> {
+        let guess = parse_guess(guess)?;
+
+        Ok(GuessEvaluation::weighted(
+            &guess,
+            self.word_picker.remaining_words(),
+            self.word_picker.priors(),
+        ))
+    }
+}
+
+/// Write a turn back as its guess and reply symbols
+pub fn format_turn(turn: &Turn) -> (String, String) {
+    let guess = turn.iter().map(|cell| cell.letter).collect();
+    let response = turn.iter().map(|cell| cell.reply).collect();
+
+    (guess, format_response(&response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 4] = ["crane", "crate", "grate", "fuzzy"];
+
+    fn solver() -> Solver {
+        Solver::new(WORDS.iter().map(|word| word.to_string()).collect())
+    }
+
+    #[test]
+    fn contradictory_turns_are_rejected() {
+        let mut solver = solver();
+        solver
+            .apply_turn(parse_turn("crane", "+++.+").unwrap())
+            .unwrap();
+
+        let result = solver.apply_turn(parse_turn("grate", ".....").unwrap());
+
+        assert_eq!(result, Err(SolverError::ContradictoryTurn));
+        assert_eq!(solver.turns().len(), 1);
+        assert_eq!(solver.word_picker().remaining_words(), ["crate"]);
+    }
+
+    #[test]
+    fn undo_replays_the_previous_turns() {
+        let mut solver = solver();
+        let first = parse_turn("fuzzy", ".....").unwrap();
+        let second = parse_turn("crane", "+++.+").unwrap();
+        solver.apply_turn(first.clone()).unwrap();
+        solver.apply_turn(second.clone()).unwrap();
+
+        assert_eq!(solver.undo(), Ok(second));
+        assert_eq!(solver.turns(), [first]);
+        assert_eq!(
+            solver.word_picker().remaining_words(),
+            ["crane", "crate", "grate"]
+        );
+    }
+
+    #[test]
+    fn undo_without_turns_fails() {
+        let mut solver = solver();
+
+        assert_eq!(solver.undo(), Err(SolverError::NothingToUndo));
+        assert_eq!(solver.word_picker().remaining(), WORDS.len());
+    }
+}
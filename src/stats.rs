@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use crate::word_picker::{unique_letters_per_word, HardModeWordPicker, ALPHA_LEN};
+use crate::NUM_LETTERS;
+
+/// Letter statistics of a dictionary, letters are indexed by position in [crate::word_picker::ALPHABET]
+#[derive(Debug, Clone)]
+pub struct DictionaryStats {
+    /// The words the stats were computed over (lowercase, [NUM_LETTERS] letters)
+    pub words: Vec<String>,
+    /// How many words contain each letter
+    pub letter_frequencies: [usize; ALPHA_LEN],
+    /// How many words have each letter in each position
+    pub positional_frequencies: [[usize; ALPHA_LEN]; NUM_LETTERS],
+    /// How many words have each number of distinct letters, index 5 is a word without repeats
+    pub distinct_letters: [usize; NUM_LETTERS + 1],
+    /// How many words contain each letter more than once
+    pub repeated_letters: [usize; ALPHA_LEN],
+    /// Every pair of adjacent letters with how many times it occurs, most common first
+    pub bigrams: Vec<(String, usize)>,
+}
+
+impl DictionaryStats {
+    pub fn new<S: AsRef<str>>(word_list: &[S]) -> Self {
+        let word_picker = HardModeWordPicker::new(word_list.iter().map(AsRef::as_ref));
+        let words = word_picker.remaining_words().to_vec();
+
+        let mut positional_frequencies = [[0; ALPHA_LEN]; NUM_LETTERS];
+        let mut distinct_letters = [0; NUM_LETTERS + 1];
+        let mut repeated_letters = [0; ALPHA_LEN];
+        let mut bigrams: HashMap<&str, usize> = HashMap::new();
+        for word in &words {
+            for (position, letter) in word.bytes().enumerate() {
+                positional_frequencies[position][(letter - b'a') as usize] += 1;
+            }
+
+            let letter_counts = unique_letters_per_word(word);
+            distinct_letters[letter_counts.iter().filter(|&&count| count > 0).count()] += 1;
+            for (repeated, &count) in repeated_letters.iter_mut().zip(&letter_counts) {
+                if count > 1 {
+                    *repeated += 1;
+                }
+            }
+
+            for start in 0..NUM_LETTERS - 1 {
+                *bigrams.entry(&word[start..start + 2]).or_default() += 1;
+            }
+        }
+        let mut bigrams: Vec<_> = bigrams
+            .into_iter()
+            .map(|(bigram, count)| (bigram.to_string(), count))
+            .collect();
+        bigrams.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+
+        Self {
+            letter_frequencies: word_picker.letter_frequencies(),
+            words,
+            positional_frequencies,
+            distinct_letters,
+            repeated_letters,
+            bigrams,
+        }
+    }
+
+    /// Words made of the rarest letters, rarest first, scored by how many words contain each of their letters (summed per letter)
+    pub fn rarest_words(&self, count: usize) -> Vec<(&str, usize)> {
+        let mut words: Vec<_> = self
+            .words
+            .iter()
+            .map(|word| {
+                let commonness = unique_letters_per_word(word)
+                    .into_iter()
+                    .zip(self.letter_frequencies)
+                    .map(|(letter_count, frequency)| letter_count as usize * frequency)
+                    .sum::<usize>();
+                (word.as_str(), commonness)
+            })
+            .collect();
+        words.sort_unstable_by_key(|&(word, commonness)| (commonness, word));
+        words.truncate(count);
+
+        words
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(letter: u8) -> usize {
+        (letter - b'a') as usize
+    }
+
+    #[test]
+    fn letters_are_counted_per_word() {
+        let stats = DictionaryStats::new(&["crane", "geese"]);
+
+        assert_eq!(stats.letter_frequencies[index(b'e')], 2);
+        assert_eq!(stats.letter_frequencies[index(b'z')], 0);
+        assert_eq!(stats.positional_frequencies[0][index(b'c')], 1);
+        assert_eq!(stats.positional_frequencies[4][index(b'e')], 2);
+        assert_eq!(stats.distinct_letters, [0, 0, 0, 1, 0, 1]);
+        assert_eq!(stats.repeated_letters[index(b'e')], 1);
+        assert_eq!(stats.repeated_letters.iter().sum::<usize>(), 1);
+    }
+
+    #[test]
+    fn bigrams_and_rarest_words() {
+        let stats = DictionaryStats::new(&["crane", "geese"]);
+
+        let bigrams: Vec<_> = stats.bigrams.iter().map(|(bigram, _)| bigram).collect();
+        assert_eq!(bigrams, ["an", "cr", "ee", "es", "ge", "ne", "ra", "se"]);
+        assert!(stats.bigrams.iter().all(|&(_, count)| count == 1));
+        assert_eq!(stats.rarest_words(1), [("crane", 6)]);
+    }
+}
@@ -0,0 +1,142 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::evaluation::GuessEvaluation;
+use crate::word_picker::{unique_letters_per_word, HardModeWordPicker};
+
+/// How the next guess is chosen from the remaining potential solutions
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Strategy {
+    /// The picker's own ranking, the first of [HardModeWordPicker::top_10_words]
+    Picker,
+    /// The word whose letters appear in the most remaining words (weighed by their priors)
+    LetterFrequency,
+    /// The word with the most expected information, the alphabetically first of equally good words
+    Entropy,
+    /// The word leaving the fewest words in the worst case
+    Minimax,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 4] = [
+        Strategy::Picker,
+        Strategy::LetterFrequency,
+        Strategy::Entropy,
+        Strategy::Minimax,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Strategy::Picker => "picker",
+            Strategy::LetterFrequency => "letter-frequency",
+            Strategy::Entropy => "entropy",
+            Strategy::Minimax => "minimax",
+        }
+    }
+
+    /// Choose the next guess from the remaining words, `None` once no words remain
+    ///
+    /// Words weighed with [HardModeWordPicker::with_priors] count by their prior probability of being the solution.
+    pub fn pick<'a>(&self, word_picker: &'a HardModeWordPicker) -> Option<&'a String> {
+        let candidates = word_picker.remaining_words();
+        let priors = word_picker.priors();
+        match self {
+            Strategy::Picker => word_picker.top_10_words().next(),
+            Strategy::LetterFrequency => {
+                let weights = word_picker.letter_weights();
+                let score = |word: &str| {
+                    unique_letters_per_word(word)
+                        .into_iter()
+                        .zip(weights)
+                        .filter(|&(count, _)| count > 0)
+                        .map(|(_, weight)| weight)
+                        .sum::<f64>()
+                };
+                candidates
+                    .iter()
+                    .max_by(|a, b| score(a).partial_cmp(&score(b)).unwrap_or(Ordering::Equal))
+            }
+            // Ties go to the alphabetically first word, like the opening book generated by build.rs
+            Strategy::Entropy => candidates
+                .iter()
+                .map(|word| (word, GuessEvaluation::weighted(word, candidates, priors)))
+                .max_by(|(a_word, a), (b_word, b)| {
+                    a.entropy
+                        .partial_cmp(&b.entropy)
+                        .unwrap_or(Ordering::Equal)
+                        .then_with(|| b_word.cmp(a_word))
+                })
+                .map(|(word, _)| word),
+            Strategy::Minimax => candidates
+                .iter()
+                .map(|word| (word, GuessEvaluation::weighted(word, candidates, priors)))
+                .min_by(|(_, a), (_, b)| {
+                    a.largest_bucket.cmp(&b.largest_bucket).then(
+                        a.expected_remaining
+                            .partial_cmp(&b.expected_remaining)
+                            .unwrap_or(Ordering::Equal),
+                    )
+                })
+                .map(|(word, _)| word),
+        }
+    }
+}
+
+impl Display for Strategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Strategy name was not recognized
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnknownStrategy(pub String);
+
+impl Display for UnknownStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<_> = Strategy::ALL.iter().map(|s| s.name()).collect();
+        write!(
+            f,
+            "unknown strategy {:?}, expected one of {}",
+            self.0,
+            names.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnknownStrategy {}
+
+impl FromStr for Strategy {
+    type Err = UnknownStrategy;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Strategy::ALL
+            .into_iter()
+            .find(|strategy| strategy.name() == name)
+            .ok_or_else(|| UnknownStrategy(name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn priors_change_the_pick() {
+        let word_picker = HardModeWordPicker::new(["crane", "crate", "fuzzy"]);
+        let priors = [("crane", 1.0), ("crate", 1.0), ("fuzzy", 100.0)]
+            .into_iter()
+            .map(|(word, weight)| (word.to_string(), weight))
+            .collect();
+        let weighted = word_picker.clone().with_priors(Arc::new(priors));
+
+        assert_eq!(
+            Strategy::LetterFrequency.pick(&word_picker).unwrap(),
+            "crate"
+        );
+        assert_eq!(Strategy::LetterFrequency.pick(&weighted).unwrap(), "fuzzy");
+    }
+}
@@ -0,0 +1,239 @@
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use crate::dictionary::Priors;
+use crate::simulation::SimulationReport;
+use crate::strategy::Strategy;
+
+/// A strategy, optionally forced to open with a fixed word
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Contestant {
+    pub strategy: Strategy,
+    pub opener: Option<String>,
+}
+
+impl Contestant {
+    /// Name used in tables and csv headers (ie. `entropy` or `entropy/crane`)
+    pub fn name(&self) -> String {
+        match &self.opener {
+            Some(opener) => format!("{}/{}", self.strategy, opener),
+            None => self.strategy.to_string(),
+        }
+    }
+}
+
+/// Summary of how a contestant did over every answer
+#[derive(Debug, Copy, Clone)]
+pub struct Standing {
+    pub mean: f64,
+    pub median: usize,
+    pub p95: usize,
+    /// Games not won within [crate::MAX_GUESSES]
+    pub failures: usize,
+}
+
+/// An answer both contestants solved, with how many guesses each needed
+#[derive(Debug, Clone)]
+pub struct AnswerDifference<'a> {
+    pub answer: &'a str,
+    pub a_guesses: usize,
+    pub b_guesses: usize,
+}
+
+/// Answers where one contestant needed fewer guesses than the other
+#[derive(Debug, Clone)]
+pub struct HeadToHead<'a> {
+    pub a_better: Vec<AnswerDifference<'a>>,
+    pub b_better: Vec<AnswerDifference<'a>>,
+    /// Answers solved in the same number of guesses
+    pub ties: usize,
+}
+
+/// Every contestant played over the same answers
+#[derive(Debug, Clone)]
+pub struct TournamentReport {
+    pub contestants: Vec<Contestant>,
+    /// One report per contestant, in the same order, each with one game per answer
+    pub reports: Vec<SimulationReport>,
+}
+
+impl TournamentReport {
+    pub fn run<S: AsRef<str>>(
+        word_list: &[String],
+        answers: &[S],
+        contestants: Vec<Contestant>,
+        priors: Option<Arc<Priors>>,
+    ) -> Self {
+        let reports = contestants
+            .iter()
+            .map(|contestant| {
+                SimulationReport::run(
+                    word_list,
+                    answers,
+                    contestant.strategy,
+                    contestant.opener.as_deref(),
+                    priors.clone(),
+                )
+            })
+            .collect();
+
+        Self {
+            contestants,
+            reports,
+        }
+    }
+
+    pub fn standing(&self, contestant: usize) -> Standing {
+        let report = &self.reports[contestant];
+
+        Standing {
+            mean: report.average_guesses(),
+            median: report.percentile(0.5),
+            p95: report.percentile(0.95),
+            failures: report.failures().count(),
+        }
+    }
+
+    /// Compare two contestants answer by answer
+    pub fn head_to_head(&self, a: usize, b: usize) -> HeadToHead<'_> {
+        let mut head_to_head = HeadToHead {
+            a_better: Vec::new(),
+            b_better: Vec::new(),
+            ties: 0,
+        };
+        let games = self.reports[a].games.iter().zip(&self.reports[b].games);
+        for (a_game, b_game) in games.filter(|(a, b)| a.solved() && b.solved()) {
+            let difference = AnswerDifference {
+                answer: &a_game.answer,
+                a_guesses: a_game.guesses.len(),
+                b_guesses: b_game.guesses.len(),
+            };
+            if difference.a_guesses < difference.b_guesses {
+                head_to_head.a_better.push(difference);
+            } else if difference.b_guesses < difference.a_guesses {
+                head_to_head.b_better.push(difference);
+            } else {
+                head_to_head.ties += 1;
+            }
+        }
+
+        head_to_head
+    }
+
+    /// Write one row per answer with the guesses each contestant needed (empty if never solved)
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let names: Vec<_> = self.contestants.iter().map(Contestant::name).collect();
+        writeln!(writer, "answer,{}", names.join(","))?;
+
+        let answers = self.reports.first().map(|report| report.games.len());
+        for index in 0..answers.unwrap_or(0) {
+            let counts: Vec<_> = self
+                .reports
+                .iter()
+                .map(|report| {
+                    let game = &report.games[index];
+                    if game.solved() {
+                        game.guesses.len().to_string()
+                    } else {
+                        String::new()
+                    }
+                })
+                .collect();
+            writeln!(
+                writer,
+                "{},{}",
+                self.reports[0].games[index].answer,
+                counts.join(",")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::GameResult;
+
+    /// A game solved in `count` guesses, or never solved if `count` is 0
+    fn game(answer: &str, count: usize) -> GameResult {
+        let mut guesses = vec![String::from("xxxxx"); count.max(1)];
+        if count > 0 {
+            guesses[count - 1] = answer.to_string();
+        }
+
+        GameResult {
+            answer: answer.to_string(),
+            guesses,
+        }
+    }
+
+    fn report(counts: &[usize]) -> SimulationReport {
+        let answers = ["crane", "slate", "trace", "audio"];
+
+        SimulationReport {
+            games: answers
+                .iter()
+                .zip(counts)
+                .map(|(answer, &count)| game(answer, count))
+                .collect(),
+        }
+    }
+
+    fn tournament() -> TournamentReport {
+        TournamentReport {
+            contestants: vec![
+                Contestant {
+                    strategy: Strategy::Picker,
+                    opener: None,
+                },
+                Contestant {
+                    strategy: Strategy::Entropy,
+                    opener: Some(String::from("crane")),
+                },
+            ],
+            reports: vec![report(&[1, 3, 2, 0]), report(&[1, 2, 4, 5])],
+        }
+    }
+
+    #[test]
+    fn standings_summarize_the_solved_games() {
+        let tournament = tournament();
+
+        let picker = tournament.standing(0);
+        assert_eq!(picker.mean, 2.0);
+        assert_eq!((picker.median, picker.p95, picker.failures), (2, 3, 1));
+
+        let entropy = tournament.standing(1);
+        assert_eq!(entropy.mean, 3.0);
+        assert_eq!((entropy.median, entropy.p95, entropy.failures), (2, 5, 0));
+    }
+
+    #[test]
+    fn head_to_head_counts_ties_and_skips_unsolved_answers() {
+        let tournament = tournament();
+        let head_to_head = tournament.head_to_head(0, 1);
+
+        let answers = |differences: &[AnswerDifference]| -> Vec<String> {
+            differences
+                .iter()
+                .map(|difference| difference.answer.to_string())
+                .collect()
+        };
+        assert_eq!(answers(&head_to_head.a_better), ["trace"]);
+        assert_eq!(answers(&head_to_head.b_better), ["slate"]);
+        assert_eq!(head_to_head.ties, 1);
+    }
+
+    #[test]
+    fn csv_has_a_column_per_contestant() {
+        let mut csv = Vec::new();
+        tournament().write_csv(&mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "answer,picker,entropy/crane\ncrane,1,1\nslate,3,2\ntrace,2,4\naudio,,5\n"
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use clap::ArgEnum;
+use wordle_helper::decision_tree::build_tree;
+use wordle_helper::strategy::Strategy;
+
+/// How the decision tree is written
+#[derive(Eq, PartialEq, Debug, Copy, Clone, ArgEnum)]
+pub enum TreeFormat {
+    /// Nested objects keyed by reply, stable order so trees can be diffed
+    Json,
+    /// Graphviz digraph, render with `dot -Tsvg`
+    Dot,
+}
+
+/// Build the strategy's complete play tree, write it to a file (or stdout) and print a summary to stderr
+pub fn run_tree(
+    word_list: &[String],
+    strategy: Strategy,
+    opener: Option<&str>,
+    format: TreeFormat,
+    output: Option<&Path>,
+) -> io::Result<()> {
+    let tree = match build_tree(word_list, strategy, opener) {
+        Some(tree) => tree,
+        None => {
+            eprintln!("dictionary is empty, no tree to build");
+            return Ok(());
+        }
+    };
+
+    let writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout()),
+    };
+    let mut writer = BufWriter::new(writer);
+    match format {
+        TreeFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, &tree)?;
+            writeln!(writer)?;
+        }
+        TreeFormat::Dot => tree.write_dot(&mut writer)?,
+    }
+    writer.flush()?;
+
+    let counts = tree.guess_counts();
+    let total: usize = counts.values().sum();
+    let deepest = counts.values().copied().max().unwrap_or(0);
+    eprintln!(
+        "Tree solves {}/{} words, {:.3} guesses on average, at most {}",
+        counts.len(),
+        word_list.len(),
+        total as f64 / counts.len().max(1) as f64,
+        deepest
+    );
+
+    Ok(())
+}
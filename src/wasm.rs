@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::game::{format_response, response_for};
+use crate::report::{suggestions, TurnReport};
+use crate::solver::{format_turn, parse_guess, parse_turn, Solver};
+use crate::{default_word_list, MAX_GUESSES, NUM_LETTERS};
+
+fn to_js<T: Serialize + ?Sized>(value: &T) -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(value).map_err(|error| JsError::new(&error.to_string()))
+}
+
+/// Word length for guesses and the dictionary
+#[wasm_bindgen(js_name = numLetters)]
+pub fn num_letters() -> usize {
+    NUM_LETTERS
+}
+
+/// Maximum numbers of guesses in a game
+#[wasm_bindgen(js_name = maxGuesses)]
+pub fn max_guesses() -> usize {
+    MAX_GUESSES
+}
+
+/// The reply (ie. `+.-..`) the game would give for a guess if `solution` was the answer
+#[wasm_bindgen(js_name = replyFor)]
+pub fn reply_for(guess: &str, solution: &str) -> Result<String, JsError> {
+    let guess = parse_guess(guess)?;
+    let solution = parse_guess(solution)?;
+
+    Ok(format_response(&response_for(&guess, &solution)))
+}
+
+/// A single game for JavaScript callers, replies are written as `+` (hit), `-` (partial) and `.` (miss)
+#[wasm_bindgen(js_name = Solver)]
+pub struct WasmSolver {
+    solver: Solver,
+}
+
+#[wasm_bindgen(js_class = Solver)]
+impl WasmSolver {
+    /// Create a solver using the embedded dictionary, fails if it was built without one (`embed-answers`)
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Result<WasmSolver, JsError> {
+        let word_list = default_word_list();
+        if word_list.is_empty() {
+            return Err(JsError::new(
+                "no embedded dictionary, use withWordList or build with the embed-answers feature",
+            ));
+        }
+
+        Ok(Self {
+            solver: Solver::new(word_list),
+        })
+    }
+
+    /// Create a solver using a custom dictionary
+    #[wasm_bindgen(js_name = withWordList)]
+    pub fn with_word_list(word_list: Vec<String>) -> WasmSolver {
+        Self {
+            solver: Solver::new(word_list),
+        }
+    }
+
+    /// Create a solver using a custom dictionary where `weights[i]` is the prior probability of `words[i]`
+    #[wasm_bindgen(js_name = withWeightedWordList)]
+    pub fn with_weighted_word_list(
+        words: Vec<String>,
+        weights: Vec<f64>,
+    ) -> Result<WasmSolver, JsError> {
+        if words.len() != weights.len() {
+            return Err(JsError::new(&format!(
+                "expected a weight for each of the {} words, got {}",
+                words.len(),
+                weights.len()
+            )));
+        }
+        let priors = words.iter().cloned().zip(weights).collect();
+
+        Ok(Self {
+            solver: Solver::new(words).with_priors(Arc::new(priors)),
+        })
+    }
+
+    /// Start over with every word in the dictionary
+    pub fn reset(&mut self) {
+        self.solver.reset();
+    }
+
+    /// How many potential solutions remain
+    #[wasm_bindgen(getter)]
+    pub fn remaining(&self) -> usize {
+        self.solver.word_picker().remaining()
+    }
+
+    /// Apply a guess and its reply, returning the state after the turn
+    #[wasm_bindgen(js_name = applyTurn)]
+    pub fn apply_turn(&mut self, guess: &str, reply: &str) -> Result<JsValue, JsError> {
+        self.solver.apply_turn(parse_turn(guess, reply)?)?;
+
+        self.report()
+    }
+
+    /// Remove the last turn, returning it as `{ guess, reply }`
+    pub fn undo(&mut self) -> Result<JsValue, JsError> {
+        let (guess, reply) = format_turn(&self.solver.undo()?);
+
+        to_js(&serde_json::json!({ "guess": guess, "reply": reply }))
+    }
+
+    /// The state after the latest turn
+    pub fn report(&self) -> Result<JsValue, JsError> {
+        to_js(&TurnReport::new(
+            self.solver.turns().len(),
+            self.solver.word_picker(),
+        ))
+    }
+
+    /// Ranked best guesses as `{ word, score, probability }`, best first
+    pub fn suggestions(&self) -> Result<JsValue, JsError> {
+        to_js(&suggestions(self.solver.word_picker()))
+    }
+
+    /// Every remaining potential solution
+    pub fn candidates(&self) -> Vec<String> {
+        self.solver.word_picker().remaining_words().to_vec()
+    }
+
+    /// How well a guess would split the remaining potential solutions
+    #[wasm_bindgen(js_name = evaluateGuess)]
+    pub fn evaluate_guess(&self, guess: &str) -> Result<JsValue, JsError> {
+        to_js(&self.solver.evaluate_guess(guess)?)
+    }
+}
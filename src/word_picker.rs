@@ -1,17 +1,33 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::iter::{Rev, Take};
 use std::slice::Iter;
+use std::sync::Arc;
 
 use arrayvec::ArrayVec;
 use log::trace;
 
+use crate::constraints::Constraints;
+use crate::dictionary::{Priors, DEFAULT_WEIGHT};
+use crate::elimination::{Elimination, Rule};
 use crate::game::GameCell;
 use crate::game::Reply;
 use crate::game::Turn;
 use crate::NUM_LETTERS;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct HardModeWordPicker {
     remaining_words: Vec<String>,
+    constraints: Constraints,
+    /// Prior weight of each word being the solution, every word is equally likely without
+    priors: Option<Arc<Priors>>,
+    /// Sum of the priors of the remaining words, kept up to date as words are removed
+    prior_total: f64,
+    turns_taken: usize,
+    /// The turn being taken while its reply removes words
+    current_turn: Option<usize>,
+    /// Why each removed word was removed, only recorded with [HardModeWordPicker::with_audit_trail]
+    eliminations: Option<HashMap<String, Elimination>>,
 }
 
 impl HardModeWordPicker {
@@ -27,7 +43,15 @@ impl HardModeWordPicker {
                 .map(|s| s.as_ref().to_ascii_lowercase()),
         );
 
-        let mut words = Self { remaining_words };
+        let mut words = Self {
+            remaining_words,
+            constraints: Constraints::default(),
+            priors: None,
+            prior_total: 0.0,
+            turns_taken: 0,
+            current_turn: None,
+            eliminations: None,
+        };
 
         // Sort words with most unique letters towards the end
         words
@@ -37,6 +61,98 @@ impl HardModeWordPicker {
         words
     }
 
+    /// Weigh words by their prior probability of being the solution, the likeliest words rank best
+    pub fn with_priors(mut self, priors: Arc<Priors>) -> Self {
+        self.priors = Some(priors);
+        // Stable sort keeps the existing order between equally likely words
+        let mut remaining_words = std::mem::take(&mut self.remaining_words);
+        remaining_words.sort_by(|a, b| {
+            self.prior(a)
+                .partial_cmp(&self.prior(b))
+                .unwrap_or(Ordering::Equal)
+        });
+        self.remaining_words = remaining_words;
+        self.update_prior_total();
+
+        self
+    }
+
+    fn update_prior_total(&mut self) {
+        self.prior_total = self
+            .remaining_words
+            .iter()
+            .map(|word| self.prior(word))
+            .sum();
+    }
+
+    /// Record why each word is removed, to explain it later with [HardModeWordPicker::elimination]
+    pub fn with_audit_trail(mut self) -> Self {
+        self.eliminations = Some(HashMap::new());
+
+        self
+    }
+
+    /// Stop recording why words are removed, dropping what was recorded
+    pub fn without_audit_trail(mut self) -> Self {
+        self.eliminations = None;
+
+        self
+    }
+
+    /// Why a word was removed, `None` if it remains, was never in the dictionary or there is no audit trail
+    pub fn elimination(&self, word: &str) -> Option<&Elimination> {
+        self.eliminations.as_ref()?.get(word)
+    }
+
+    /// Keep the words matching `keep`, recording the rule that removed the others when auditing
+    fn retain_words<F: Fn(&str) -> bool>(&mut self, rule: Rule, keep: F) {
+        let turn = self.current_turn;
+        let eliminations = &mut self.eliminations;
+        self.remaining_words.retain(|word| {
+            let kept = keep(word);
+            if let (false, Some(eliminations)) = (kept, eliminations.as_mut()) {
+                eliminations.insert(word.clone(), Elimination { turn, rule });
+            }
+
+            kept
+        });
+        if self.priors.is_some() {
+            self.update_prior_total();
+        }
+    }
+
+    /// Prior weight of a word, [DEFAULT_WEIGHT] without priors or for words missing from them
+    pub fn prior(&self, word: &str) -> f64 {
+        self.priors
+            .as_ref()
+            .and_then(|priors| priors.get(word).copied())
+            .unwrap_or(DEFAULT_WEIGHT)
+    }
+
+    /// If the words were weighed with [HardModeWordPicker::with_priors]
+    pub fn has_priors(&self) -> bool {
+        self.priors.is_some()
+    }
+
+    /// The priors the words were weighed with, if any
+    pub fn priors(&self) -> Option<&Priors> {
+        self.priors.as_deref()
+    }
+
+    /// Estimated probability of a remaining word being the solution, its prior relative to all remaining words
+    pub fn probability(&self, word: &str) -> f64 {
+        let total = if self.priors.is_some() {
+            self.prior_total
+        } else {
+            self.remaining_words.len() as f64 * DEFAULT_WEIGHT
+        };
+        if total > 0.0 {
+            self.prior(word) / total
+        } else {
+            0.0
+        }
+    }
+
     pub fn number_of_words_containing_letter(&self, letter: char) -> usize {
         self.remaining_words
             .iter()
@@ -53,24 +169,55 @@ impl HardModeWordPicker {
         frequencies.into_inner().unwrap()
     }
 
+    /// Summed prior of the remaining words containing each letter, indexed like [ALPHABET]
+    ///
+    /// The same as [HardModeWordPicker::letter_frequencies] without priors.
+    pub fn letter_weights(&self) -> [f64; ALPHA_LEN] {
+        let mut weights = [0.0; ALPHA_LEN];
+        for word in &self.remaining_words {
+            let prior = self.prior(word);
+            for (weight, count) in weights.iter_mut().zip(unique_letters_per_word(word)) {
+                if count > 0 {
+                    *weight += prior;
+                }
+            }
+        }
+
+        weights
+    }
+
     /// How many potential solutions remain
     pub fn remaining(&self) -> usize {
         self.remaining_words.len()
     }
 
+    /// The potential solutions that remain
+    pub fn remaining_words(&self) -> &[String] {
+        &self.remaining_words
+    }
+
+    /// What is known about the solution from the turns taken so far
+    pub fn constraints(&self) -> &Constraints {
+        &self.constraints
+    }
+
     /// Remove words based on a turn
     pub fn take_turn(&mut self, turn: Turn) {
+        self.turns_taken += 1;
+        self.current_turn = Some(self.turns_taken);
         for (index, &GameCell { reply, letter }) in turn.iter().enumerate() {
             match reply {
                 Reply::Success => {
                     // If we get a letter in the correct spot remove all words that dont
                     self.remove_words_without_letter_in_position(letter, index);
+                    self.constraints.place(letter, index);
                 }
                 Reply::Partial => {
                     // If we get a partial match we know the word must contain this letter
                     self.remove_words_not_containing(letter);
                     // We also know this position isn't correct for this letter
                     self.remove_words_with_letter_in_position(letter, index);
+                    self.constraints.misplace(letter, index);
                 }
                 Reply::Miss => {
                     // Check if this is a matched repeated letter; meaning, if there is another occurrence that is a success/partial
@@ -93,12 +240,51 @@ impl HardModeWordPicker {
                     // If we have a matched repeat, we can only remove this letter in this position because there could be a second letter so we can't remove them all.
                     if has_matching_repeat {
                         self.remove_words_with_letter_in_position(letter, index);
+                        self.constraints.exclude_position(letter, index);
                     } else {
                         self.remove_words_containing(letter);
+                        self.constraints.exclude(letter);
                     }
                 }
             }
         }
+        self.current_turn = None;
+    }
+    /// Remove words based on constraints known without their turns (ie. read off a board halfway through a game)
+    ///
+    /// Absent letters that are also required (by these constraints or by earlier turns) are taken to have no further copies,
+    /// like a miss with a matching repeat, and are not removed.
+    pub fn apply_constraints(&mut self, constraints: &Constraints) {
+        for (index, letter) in constraints.placed.iter().enumerate() {
+            if let Some(letter) = *letter {
+                self.remove_words_without_letter_in_position(letter, index);
+                self.constraints.place(letter, index);
+            }
+        }
+        for &letter in &constraints.required {
+            self.remove_words_not_containing(letter);
+            self.constraints.required.insert(letter);
+        }
+        for (&letter, positions) in &constraints.excluded_positions {
+            for &index in positions {
+                self.remove_words_with_letter_in_position(letter, index);
+                self.constraints.exclude_position(letter, index);
+            }
+        }
+        for &letter in &constraints.absent {
+            // Placed letters are required too, and these constraints were merged in above
+            if !self.constraints.required.contains(&letter) {
+                self.remove_words_containing(letter);
+                self.constraints.exclude(letter);
+            }
+        }
+    }
+
+    /// Remove words that cannot be the solution for reasons outside the game (ie. answers the daily game already used)
+    pub fn remove_words(&mut self, words: &HashSet<String>) {
+        trace!("removing {} words", words.len());
+        self.retain_words(Rule::Excluded, |word| !words.contains(word));
+        trace!("has {} remaining words", self.remaining_words.len());
     }
     pub fn remove_words_containing(&mut self, illegal_letter: char) {
         trace!("removing {} from all positions", illegal_letter);
@@ -108,8 +294,9 @@ impl HardModeWordPicker {
         );
         debug_assert!(illegal_letter.is_lowercase(), "letter must be lowercase");
 
-        self.remaining_words
-            .retain(|word| !word.contains(illegal_letter));
+        self.retain_words(Rule::Containing(illegal_letter), |word| {
+            !word.contains(illegal_letter)
+        });
         trace!(
             "has {} remaining words {:?}",
             self.remaining_words.len(),
@@ -137,8 +324,10 @@ impl HardModeWordPicker {
         );
         let required_letter = required_letter.to_ascii_lowercase();
 
-        self.remaining_words
-            .retain(|word| word.chars().nth(required_position) == Some(required_letter));
+        self.retain_words(
+            Rule::WithoutLetterInPosition(required_letter, required_position),
+            |word| word.chars().nth(required_position) == Some(required_letter),
+        );
         trace!(
             "has {} remaining words {:?}",
             self.remaining_words.len(),
@@ -166,8 +355,10 @@ impl HardModeWordPicker {
         );
         let illegal_letter = illegal_letter.to_ascii_lowercase();
 
-        self.remaining_words
-            .retain(|word| word.chars().nth(required_position) != Some(illegal_letter));
+        self.retain_words(
+            Rule::WithLetterInPosition(illegal_letter, required_position),
+            |word| word.chars().nth(required_position) != Some(illegal_letter),
+        );
         trace!(
             "has {} remaining words {:?}",
             self.remaining_words.len(),
@@ -182,8 +373,9 @@ impl HardModeWordPicker {
         );
         debug_assert!(required_letter.is_lowercase(), "letter must be lowercase");
 
-        self.remaining_words
-            .retain(|word| word.contains(required_letter));
+        self.retain_words(Rule::NotContaining(required_letter), |word| {
+            word.contains(required_letter)
+        });
 
         trace!(
             "has {} remaining words {:?}",
@@ -194,15 +386,21 @@ impl HardModeWordPicker {
     pub fn top_10_words(&self) -> Take<Rev<Iter<'_, String>>> {
         self.remaining_words.iter().rev().take(10)
     }
+    /// The top 10 words paired with the score they were ranked by (their number of distinct letters)
+    pub fn top_10_scored_words(&self) -> impl Iterator<Item = (&String, usize)> {
+        self.top_10_words()
+            .map(|word| (word, unique_letter_count_per_word(word)))
+    }
 }
 
-pub(crate) const ALPHA_LEN: usize = 26;
-pub(crate) const ALPHABET: [char; ALPHA_LEN] = [
+pub const ALPHA_LEN: usize = 26;
+pub const ALPHABET: [char; ALPHA_LEN] = [
     'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
     't', 'u', 'v', 'w', 'x', 'y', 'z',
 ];
 
-fn unique_letters_per_word(word: &str) -> [u8; ALPHA_LEN] {
+/// Count of each letter in a lowercase word, indexed by position in [ALPHABET]
+pub fn unique_letters_per_word(word: &str) -> [u8; ALPHA_LEN] {
     let mut seen = [0u8; ALPHA_LEN];
 
     debug_assert_eq!(
@@ -223,8 +421,152 @@ fn unique_letters_per_word(word: &str) -> [u8; ALPHA_LEN] {
     seen
 }
 
+/// Number of distinct letters in a lowercase word
 fn unique_letter_count_per_word(word: &str) -> usize {
     let seen = unique_letters_per_word(word);
 
-    seen.into_iter().sum::<u8>() as usize
+    seen.into_iter().filter(|&count| count > 0).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{make_turn, response_for};
+
+    #[test]
+    fn unique_letter_count_ignores_repeats() {
+        assert_eq!(unique_letter_count_per_word("crane"), 5);
+        assert_eq!(unique_letter_count_per_word("geese"), 3);
+        assert_eq!(unique_letter_count_per_word("mamma"), 2);
+    }
+
+    #[test]
+    fn words_with_repeated_letters_rank_last() {
+        let word_picker = HardModeWordPicker::new(["geese", "crane", "mamma"]);
+        let ranked: Vec<_> = word_picker.top_10_words().collect();
+
+        assert_eq!(ranked, ["crane", "geese", "mamma"]);
+    }
+
+    #[test]
+    fn suggestions_are_scored_by_distinct_letters() {
+        let word_picker = HardModeWordPicker::new(["geese", "crane", "mamma"]);
+        let scored: Vec<_> = word_picker
+            .top_10_scored_words()
+            .map(|(word, score)| (word.as_str(), score))
+            .collect();
+
+        assert_eq!(scored, [("crane", 5), ("geese", 3), ("mamma", 2)]);
+    }
+
+    #[test]
+    fn absent_letters_already_found_are_kept() {
+        let mut word_picker = HardModeWordPicker::new(["crane", "salty", "adept"]);
+        word_picker.take_turn(make_turn("aback", &response_for("aback", "salty")));
+        assert_eq!(word_picker.remaining_words(), ["salty"]);
+
+        word_picker.apply_constraints(&"gray a".parse().unwrap());
+        assert_eq!(word_picker.remaining_words(), ["salty"]);
+    }
+
+    fn audited(words: &[&str]) -> HardModeWordPicker {
+        HardModeWordPicker::new(words.iter().copied()).with_audit_trail()
+    }
+
+    fn in_turn(turn: usize, rule: Rule) -> Option<Elimination> {
+        Some(Elimination {
+            turn: Some(turn),
+            rule,
+        })
+    }
+
+    #[test]
+    fn eliminations_name_the_reply_that_removed_the_word() {
+        let mut word_picker = audited(&["caper", "brace", "cider", "crepe", "caner"]);
+        word_picker.take_turn(make_turn("crane", &response_for("crane", "caper")));
+
+        assert_eq!(word_picker.remaining_words(), ["caper"]);
+        assert_eq!(word_picker.elimination("caper"), None);
+        // Green c
+        assert_eq!(
+            word_picker.elimination("brace").copied(),
+            in_turn(1, Rule::WithoutLetterInPosition('c', 0))
+        );
+        // Yellow a
+        assert_eq!(
+            word_picker.elimination("cider").copied(),
+            in_turn(1, Rule::NotContaining('a'))
+        );
+        // Yellow r
+        assert_eq!(
+            word_picker.elimination("crepe").copied(),
+            in_turn(1, Rule::WithLetterInPosition('r', 1))
+        );
+        // Gray n
+        assert_eq!(
+            word_picker.elimination("caner").copied(),
+            in_turn(1, Rule::Containing('n'))
+        );
+    }
+
+    #[test]
+    fn repeated_gray_letters_only_eliminate_their_position() {
+        let mut word_picker = audited(&["those", "these"]);
+        word_picker.take_turn(make_turn("geese", &response_for("geese", "those")));
+
+        assert_eq!(word_picker.remaining_words(), ["those"]);
+        assert_eq!(
+            word_picker.elimination("these").copied(),
+            in_turn(1, Rule::WithLetterInPosition('e', 2))
+        );
+    }
+
+    #[test]
+    fn eliminations_outside_turns_have_no_turn() {
+        let mut word_picker = audited(&["crane", "cigar", "fuzzy"]);
+        word_picker.remove_words(&HashSet::from([String::from("cigar")]));
+        word_picker.apply_constraints(&"gray z".parse().unwrap());
+
+        assert_eq!(
+            word_picker.elimination("cigar").copied(),
+            Some(Elimination {
+                turn: None,
+                rule: Rule::Excluded
+            })
+        );
+        assert_eq!(
+            word_picker.elimination("fuzzy").copied(),
+            Some(Elimination {
+                turn: None,
+                rule: Rule::Containing('z')
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_or_unaudited_words_have_no_elimination() {
+        let mut word_picker = audited(&["crane", "fuzzy"]);
+        word_picker.take_turn(make_turn("crane", &response_for("crane", "crane")));
+        assert_eq!(word_picker.elimination("zzzzz"), None);
+
+        let mut word_picker = HardModeWordPicker::new(["crane", "fuzzy"]);
+        word_picker.take_turn(make_turn("crane", &response_for("crane", "crane")));
+        assert_eq!(word_picker.remaining_words(), ["crane"]);
+        assert_eq!(word_picker.elimination("fuzzy"), None);
+    }
+
+    #[test]
+    fn probabilities_follow_removed_words() {
+        let priors = [("crane", 3.0), ("crate", 1.0), ("fuzzy", 4.0)]
+            .into_iter()
+            .map(|(word, weight)| (word.to_string(), weight))
+            .collect();
+        let mut word_picker =
+            HardModeWordPicker::new(["crane", "crate", "fuzzy"]).with_priors(Arc::new(priors));
+        assert_eq!(word_picker.probability("fuzzy"), 0.5);
+
+        word_picker.take_turn(make_turn("crane", &response_for("crane", "crate")));
+        assert_eq!(word_picker.remaining_words(), ["crate"]);
+        assert_eq!(word_picker.probability("crate"), 1.0);
+    }
 }
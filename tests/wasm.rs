@@ -0,0 +1,44 @@
+//! Run with `wasm-pack test --headless --firefox` (or `--chrome`)
+#![cfg(target_arch = "wasm32")]
+
+use wasm_bindgen_test::*;
+use wordle_helper::wasm::{reply_for, WasmSolver};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn reply_for_marks_hits_partials_and_misses() {
+    assert_eq!(reply_for("crane", "react").unwrap(), "--+.-");
+}
+
+#[wasm_bindgen_test]
+fn solver_narrows_embedded_dictionary() {
+    let mut solver = WasmSolver::new().unwrap();
+    let initial = solver.remaining();
+    solver.apply_turn("crane", ".-..+").unwrap();
+
+    assert!(solver.remaining() < initial);
+    assert!(solver
+        .candidates()
+        .iter()
+        .all(|word| reply_for("crane", word).unwrap() == ".-..+"));
+}
+
+#[wasm_bindgen_test]
+fn contradictory_turn_is_rejected() {
+    let mut solver = WasmSolver::new().unwrap();
+    solver.apply_turn("crane", "+++++").unwrap();
+
+    assert!(solver.apply_turn("crane", ".....").is_err());
+    assert_eq!(solver.remaining(), 1);
+}
+
+#[wasm_bindgen_test]
+fn undo_restores_previous_state() {
+    let mut solver = WasmSolver::new().unwrap();
+    let initial = solver.remaining();
+    solver.apply_turn("crane", ".-..+").unwrap();
+    solver.undo().unwrap();
+
+    assert_eq!(solver.remaining(), initial);
+}